@@ -1,4 +1,5 @@
 use crate::expr_interpret::Result;
+use crate::function::Arity;
 use crate::function::LoxFunction;
 use crate::instance::LoxInstance;
 use crate::{environment::EnvironmentTree, literal::Literal};
@@ -10,6 +11,7 @@ use std::{fmt::Display, io::Write, rc::Rc};
 pub struct LoxClass {
     pub name: String,
     pub methods: HashMap<String, LoxFunction>,
+    pub static_methods: HashMap<String, LoxFunction>,
     pub superclass: Option<Rc<LoxClass>>,
 }
 
@@ -19,15 +21,30 @@ impl Display for LoxClass {
     }
 }
 
+// a set of methods that classes can mix in, which is not a class itself
+#[derive(PartialEq)]
+pub struct LoxTrait {
+    pub name: String,
+    pub methods: HashMap<String, LoxFunction>,
+}
+
+impl Display for LoxTrait {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<trait {}>", self.name)
+    }
+}
+
 impl LoxClass {
     pub fn new(
         name: String,
         methods: HashMap<String, LoxFunction>,
+        static_methods: HashMap<String, LoxFunction>,
         superclass: Option<Rc<LoxClass>>,
     ) -> Self {
         LoxClass {
             name,
             methods,
+            static_methods,
             superclass,
         }
     }
@@ -50,8 +67,10 @@ impl LoxClass {
         Ok(instance)
     }
 
-    pub fn arity(&self) -> usize {
-        self.methods.get("init").map_or(0, |m| m.arity())
+    pub fn arity(&self) -> Arity {
+        self.methods
+            .get("init")
+            .map_or(Arity::exact(0), |m| m.arity())
     }
 
     pub fn find_method(&self, name: &str) -> Option<&LoxFunction> {
@@ -59,4 +78,34 @@ impl LoxClass {
             .get(name)
             .or_else(|| self.superclass.as_ref().and_then(|sc| sc.find_method(name)))
     }
+
+    // a class counts as a subclass of itself
+    pub fn is_subclass_of(&self, other: &LoxClass) -> bool {
+        std::ptr::eq(self, other)
+            || self
+                .superclass
+                .as_ref()
+                .is_some_and(|sc| sc.is_subclass_of(other))
+    }
+
+    // methods that overload an operator, along with how many
+    // arguments the operator calls them with
+    pub fn operator_arity(name: &str) -> Option<usize> {
+        match name {
+            "__neg__" | "__str__" => Some(0),
+            "__add__" | "__sub__" | "__mul__" | "__div__" | "__mod__" | "__floordiv__"
+            | "__pow__" | "__eq__" | "__lt__" | "__le__" | "__gt__" | "__ge__" => Some(1),
+            "__radd__" | "__rsub__" | "__rmul__" | "__rdiv__" | "__rmod__" | "__rfloordiv__"
+            | "__rpow__" => Some(1),
+            _ => None,
+        }
+    }
+
+    pub fn find_static_method(&self, name: &str) -> Option<&LoxFunction> {
+        self.static_methods.get(name).or_else(|| {
+            self.superclass
+                .as_ref()
+                .and_then(|sc| sc.find_static_method(name))
+        })
+    }
 }
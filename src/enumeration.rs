@@ -0,0 +1,134 @@
+use crate::expr_interpret::Result;
+use crate::expr_interpret::RuntimeError;
+use crate::function::Arity;
+use crate::literal::Literal;
+use crate::token::Token;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+pub struct LoxEnum {
+    name: String,
+    variants: HashMap<String, Rc<LoxVariant>>,
+}
+
+// every enum declaration creates a different enum
+impl PartialEq for LoxEnum {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Display for LoxEnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<enum {}>", self.name)
+    }
+}
+
+impl LoxEnum {
+    pub fn new(name: String, variants: Vec<LoxVariant>) -> Self {
+        let variants = variants
+            .into_iter()
+            .map(|v| (v.name.clone(), Rc::new(v)))
+            .collect();
+        LoxEnum { name, variants }
+    }
+
+    // a variant without fields is a value by itself,
+    // while the others construct values
+    pub fn get(&self, name: &Token) -> Result<Rc<Literal>> {
+        let Some(variant) = self.variants.get(&name.lexeme) else {
+            return Err(RuntimeError::new(
+                name,
+                &format!("Undefined variant '{}'.", name.lexeme),
+            ));
+        };
+        if variant.fields.is_empty() {
+            let value = EnumValue {
+                variant: variant.clone(),
+                values: vec![],
+            };
+            Ok(Rc::new(Literal::EnumValueLiteral(value)))
+        } else {
+            Ok(Rc::new(Literal::VariantLiteral(variant.clone())))
+        }
+    }
+}
+
+pub struct LoxVariant {
+    enum_name: String,
+    name: String,
+    fields: Vec<String>,
+}
+
+impl PartialEq for LoxVariant {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Display for LoxVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<variant {}.{}>", self.enum_name, self.name)
+    }
+}
+
+impl LoxVariant {
+    pub fn new(enum_name: String, name: String, fields: Vec<String>) -> Self {
+        LoxVariant {
+            enum_name,
+            name,
+            fields,
+        }
+    }
+
+    pub fn arity(&self) -> Arity {
+        Arity::exact(self.fields.len())
+    }
+
+    pub fn call(self: &Rc<Self>, args: Vec<Rc<Literal>>) -> Rc<Literal> {
+        let value = EnumValue {
+            variant: self.clone(),
+            values: args,
+        };
+        Rc::new(Literal::EnumValueLiteral(value))
+    }
+}
+
+// values of the same variant are equal if their fields are
+#[derive(PartialEq)]
+pub struct EnumValue {
+    variant: Rc<LoxVariant>,
+    values: Vec<Rc<Literal>>,
+}
+
+impl Display for EnumValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.variant.enum_name, self.variant.name)?;
+        if !self.values.is_empty() {
+            let values: Vec<String> = self.values.iter().map(|v| v.to_string()).collect();
+            write!(f, "({})", values.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl EnumValue {
+    pub fn is_variant(&self, variant: &Rc<LoxVariant>) -> bool {
+        Rc::ptr_eq(&self.variant, variant)
+    }
+
+    // 'tag' is the name of the variant, which fields can't be named
+    pub fn get(&self, name: &Token) -> Result<Rc<Literal>> {
+        if name.lexeme == "tag" {
+            return Ok(Rc::new(Literal::StringLiteral(self.variant.name.clone())));
+        }
+        match self.variant.fields.iter().position(|f| *f == name.lexeme) {
+            Some(i) => Ok(self.values[i].clone()),
+            None => Err(RuntimeError::new(
+                name,
+                &format!("Undefined property '{}'.", name.lexeme),
+            )),
+        }
+    }
+}
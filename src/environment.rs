@@ -1,6 +1,8 @@
+use crate::class::LoxClass;
 use crate::expr_interpret::Result;
 use crate::expr_interpret::RuntimeError;
 use crate::literal::Literal;
+use crate::module::ModuleLoader;
 use crate::token::Token;
 use indextree::Arena;
 use indextree::NodeId;
@@ -9,29 +11,70 @@ use std::rc::Rc;
 
 pub type Environment = HashMap<String, Rc<Literal>>;
 
+// is_global marks the root as well as the global env of the main file
+// and of each module
+// consts maps the name of each constant to the line it is declared on,
+// the resolver takes care of local constants but not global ones
+// imports maps each name imported by a module's global env to the env of
+// the module it comes from, where it is looked up so that it stays live
+// module is the name of the module whose global env this is
 struct EnvironmentNode {
     map: Environment,
     keep_alive: bool,
+    is_global: bool,
+    consts: HashMap<String, usize>,
+    imports: HashMap<String, NodeId>,
+    module: Option<String>,
 }
 
+impl EnvironmentNode {
+    fn global(module: Option<String>) -> Self {
+        EnvironmentNode {
+            map: Environment::new(),
+            keep_alive: true,
+            is_global: true,
+            consts: HashMap::new(),
+            imports: HashMap::new(),
+            module,
+        }
+    }
+}
+
+// the root only holds the builtins (e.g. native functions), the main file
+// and each module have a global env of their own under it, so that none
+// of them sees the globals of another
+// modules run in envs of their own under the root, so the loader
+// that keeps track of them lives here as well
+// error_class is the class of caught built-in errors, kept here so that
+// shadowing the global RuntimeError doesn't change it
 pub struct EnvironmentTree {
     tree: Arena<EnvironmentNode>,
+    builtins_nid: NodeId,
     global_nid: NodeId,
     nid: NodeId,
+    pub modules: ModuleLoader,
+    pub error_class: Rc<LoxClass>,
 }
 
 impl EnvironmentTree {
     pub fn new() -> EnvironmentTree {
         let mut tree = Arena::new();
-        let global_nid = tree.new_node(EnvironmentNode {
-            map: Environment::new(),
-            keep_alive: true,
-        });
+        let builtins_nid = tree.new_node(EnvironmentNode::global(None));
+        let global_nid = tree.new_node(EnvironmentNode::global(None));
+        builtins_nid.append(global_nid, &mut tree);
 
         EnvironmentTree {
             tree,
+            builtins_nid,
             global_nid,
             nid: global_nid,
+            modules: ModuleLoader::default(),
+            error_class: Rc::new(LoxClass::new(
+                "RuntimeError".to_string(),
+                HashMap::new(),
+                HashMap::new(),
+                None,
+            )),
         }
     }
 
@@ -39,6 +82,10 @@ impl EnvironmentTree {
         let node = EnvironmentNode {
             map: env,
             keep_alive: false,
+            is_global: false,
+            consts: HashMap::new(),
+            imports: HashMap::new(),
+            module: None,
         };
         let child = self.tree.new_node(node);
         self.nid.append(child, &mut self.tree);
@@ -91,12 +138,42 @@ impl EnvironmentTree {
         nid
     }
 
+    // module env is a sibling of the main file's, so it only sees the builtins
+    pub fn enter_module(&mut self, name: String) -> NodeId {
+        let child = self.tree.new_node(EnvironmentNode::global(Some(name)));
+        self.builtins_nid.append(child, &mut self.tree);
+        self.checkout(child)
+    }
+
+    // returns the env of the module, which stays alive for its exports
+    pub fn leave_module(&mut self, prev: NodeId) -> NodeId {
+        self.checkout(prev)
+    }
+
+    // the module an env belongs to, None for the main file
+    pub fn module_of(&self, nid: NodeId) -> Option<&str> {
+        let global = nid
+            .ancestors(&self.tree)
+            .find(|nid| self.tree[*nid].get().is_global)?;
+        self.tree[global].get().module.as_deref()
+    }
+
+    // exports are looked up when accessed, so they see later updates
+    pub fn get_export(&self, module_nid: NodeId, key: &str) -> Option<&Rc<Literal>> {
+        let node = self.tree[module_nid].get();
+        match node.imports.get(key) {
+            // a name the module imported itself
+            Some(nid) => self.get_export(*nid, key),
+            None => node.map.get(key),
+        }
+    }
+
     pub fn get(&self, name: &Token, distance: Option<usize>) -> Result<&Rc<Literal>> {
         if let Some(d) = distance {
             if let Some(value) = self.get_value_at(self.nid, d, &name.lexeme) {
                 return Ok(value);
             }
-        } else if let Some(value) = self.get_value_at(self.global_nid, 0, &name.lexeme) {
+        } else if let Some(value) = self.get_global(&name.lexeme) {
             // var is assumed in the global if distance is None
             return Ok(value);
         }
@@ -125,7 +202,20 @@ impl EnvironmentTree {
                 *value_ref = value;
                 return Ok(());
             }
-        } else if let Some(value_ref) = self.get_value_ref_at(self.global_nid, 0, &name.lexeme) {
+        } else if let Some(line) = self.get_global_const(&name.lexeme) {
+            return Err(RuntimeError::new(
+                name,
+                &format!(
+                    "Can't assign to constant '{}' declared on line {}.",
+                    name.lexeme, line
+                ),
+            ));
+        } else if self.is_global_import(&name.lexeme) {
+            return Err(RuntimeError::new(
+                name,
+                &format!("Can't assign to imported variable '{}'.", name.lexeme),
+            ));
+        } else if let Some(value_ref) = self.get_global_ref(&name.lexeme) {
             // var is assumed in the global if distance is None
             *value_ref = value;
             return Ok(());
@@ -137,8 +227,69 @@ impl EnvironmentTree {
         ))
     }
 
+    // redeclaring a constant with 'var' makes it a variable again
     pub fn define(&mut self, name: String, value: Rc<Literal>) {
-        self.tree[self.nid].get_mut().map.insert(name, value);
+        let node = self.tree[self.nid].get_mut();
+        node.consts.remove(&name);
+        node.imports.remove(&name);
+        node.map.insert(name, value);
+    }
+
+    pub fn define_builtin(&mut self, name: String, value: Rc<Literal>) {
+        self.tree[self.builtins_nid]
+            .get_mut()
+            .map
+            .insert(name, value);
+    }
+
+    pub fn define_const(&mut self, name: String, value: Rc<Literal>, line: usize) {
+        let node = self.tree[self.nid].get_mut();
+        node.imports.remove(&name);
+        node.consts.insert(name.clone(), line);
+        node.map.insert(name, value);
+    }
+
+    // imports only happen at the top level, so this is a global env
+    pub fn define_import(&mut self, name: String, module_nid: NodeId) {
+        let node = self.tree[self.nid].get_mut();
+        node.consts.remove(&name);
+        node.map.remove(&name);
+        node.imports.insert(name, module_nid);
+    }
+
+    // globals of the enclosing module shadow the ones of the root
+    fn global_nids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.nid
+            .ancestors(&self.tree)
+            .filter(|nid| self.tree[*nid].get().is_global)
+    }
+
+    // the global env that defines or imports the name
+    fn find_global(&self, key: &str) -> Option<NodeId> {
+        self.global_nids().find(|nid| {
+            let node = self.tree[*nid].get();
+            node.map.contains_key(key) || node.imports.contains_key(key)
+        })
+    }
+
+    fn get_global(&self, key: &str) -> Option<&Rc<Literal>> {
+        self.get_export(self.find_global(key)?, key)
+    }
+
+    // the line of the constant, if the global found is a constant
+    fn get_global_const(&self, key: &str) -> Option<usize> {
+        let nid = self.find_global(key)?;
+        self.tree[nid].get().consts.get(key).copied()
+    }
+
+    fn is_global_import(&self, key: &str) -> bool {
+        self.find_global(key)
+            .is_some_and(|nid| self.tree[nid].get().imports.contains_key(key))
+    }
+
+    fn get_global_ref(&mut self, key: &str) -> Option<&mut Rc<Literal>> {
+        let nid = self.find_global(key)?;
+        self.tree[nid].get_mut().map.get_mut(key)
     }
 
     fn get_value_at(&self, nid: NodeId, offset: usize, key: &str) -> Option<&Rc<Literal>> {
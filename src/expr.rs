@@ -1,5 +1,8 @@
 use crate::literal::Literal;
+use crate::statement::FunctionStmt;
 use crate::token::Token;
+use std::cell::RefCell;
+use std::rc::Rc;
 
 pub enum Expr {
     Literal(LiteralExpr),
@@ -14,6 +17,14 @@ pub enum Expr {
     Set(SetExpr),
     This(ThisExpr),
     Super(SuperExpr),
+    Function(FunctionExpr),
+    List(ListExpr),
+    Map(MapExpr),
+    Index(IndexExpr),
+    IndexSet(IndexSetExpr),
+    Interpolation(InterpolationExpr),
+    Conditional(ConditionalExpr),
+    Destructure(DestructureExpr),
 }
 
 pub struct LiteralExpr {
@@ -39,10 +50,18 @@ pub struct VarExpr {
     pub scope_offset: Option<usize>,
 }
 
+// the operator of a compound assignment (e.g. a += 1) or of an
+// increment/decrement (e.g. a++), in which case value is always 1
+pub struct CompoundOp {
+    pub operator: Token,
+    pub postfix: bool,
+}
+
 pub struct AssignExpr {
     pub name: Token,
     pub value: Box<Expr>,
     pub scope_offset: Option<usize>,
+    pub compound: Option<CompoundOp>,
 }
 
 pub struct LogicalExpr {
@@ -60,12 +79,15 @@ pub struct CallExpr {
 pub struct GetExpr {
     pub object: Box<Expr>,
     pub name: Token,
+    // accessed with '?.', which gives nil for a nil object
+    pub optional: bool,
 }
 
 pub struct SetExpr {
     pub object: Box<Expr>,
     pub name: Token,
     pub value: Box<Expr>,
+    pub compound: Option<CompoundOp>,
 }
 
 pub struct ThisExpr {
@@ -78,3 +100,58 @@ pub struct SuperExpr {
     pub method: Token,
     pub scope_offset: Option<usize>,
 }
+
+// an anonymous function, whose name is the 'fun' keyword
+pub struct FunctionExpr {
+    pub declaration: Rc<RefCell<FunctionStmt>>,
+}
+
+pub struct ListExpr {
+    pub bracket: Token,
+    pub elements: Vec<Expr>,
+}
+
+pub struct MapExpr {
+    pub brace: Token,
+    pub entries: Vec<(Expr, Expr)>,
+}
+
+pub struct IndexExpr {
+    pub object: Box<Expr>,
+    pub bracket: Token,
+    pub index: Box<Expr>,
+}
+
+pub struct IndexSetExpr {
+    pub object: Box<Expr>,
+    pub bracket: Token,
+    pub index: Box<Expr>,
+    pub value: Box<Expr>,
+    pub compound: Option<CompoundOp>,
+}
+
+// "a ${b} c" is parsed into parts [(string "a "), (var b), (string " c")]
+pub struct InterpolationExpr {
+    pub parts: Vec<Expr>,
+}
+
+pub struct ConditionalExpr {
+    pub condition: Box<Expr>,
+    pub then_branch: Box<Expr>,
+    pub else_branch: Box<Expr>,
+}
+
+// [a, b] binds the elements of a list and {x, y} the fields of an instance
+pub struct Destructuring {
+    // '[' or '{', which errors about the value as a whole point at
+    pub bracket: Token,
+    pub names: Vec<Token>,
+}
+
+// [a, b] = value
+pub struct DestructureExpr {
+    pub pattern: Destructuring,
+    pub value: Box<Expr>,
+    // one for each name of the pattern
+    pub scope_offsets: Vec<Option<usize>>,
+}
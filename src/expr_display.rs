@@ -1,5 +1,6 @@
 use crate::expr::*;
 use crate::literal::Literal;
+use crate::token::TokenType;
 use std::fmt::Display;
 
 // TODO: probably should use the crate enum_dispatch
@@ -18,6 +19,14 @@ impl Display for Expr {
             Expr::Set(expr) => write!(f, "{}", expr),
             Expr::This(expr) => write!(f, "{}", expr),
             Expr::Super(expr) => write!(f, "{}", expr),
+            Expr::Function(expr) => write!(f, "{}", expr),
+            Expr::List(expr) => write!(f, "{}", expr),
+            Expr::Map(expr) => write!(f, "{}", expr),
+            Expr::Index(expr) => write!(f, "{}", expr),
+            Expr::IndexSet(expr) => write!(f, "{}", expr),
+            Expr::Interpolation(expr) => write!(f, "{}", expr),
+            Expr::Conditional(expr) => write!(f, "{}", expr),
+            Expr::Destructure(expr) => write!(f, "{}", expr),
         }
     }
 }
@@ -58,9 +67,39 @@ impl Display for VarExpr {
     }
 }
 
+impl Display for CompoundOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.postfix {
+            write!(f, "post{}", self.operator.lexeme)
+        } else {
+            write!(f, "{}", self.operator.lexeme)
+        }
+    }
+}
+
 impl Display for AssignExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "(assign {} {})", self.name.lexeme, self.value)
+        match self.compound {
+            Some(ref c) => write!(f, "(assign{} {} {})", c, self.name.lexeme, self.value),
+            None => write!(f, "(assign {} {})", self.name.lexeme, self.value),
+        }
+    }
+}
+
+impl Display for Destructuring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self.names.iter().map(|n| n.lexeme.as_str()).collect();
+        if self.bracket.token_type == TokenType::LEFT_BRACKET {
+            write!(f, "[{}]", names.join(" "))
+        } else {
+            write!(f, "{{{}}}", names.join(" "))
+        }
+    }
+}
+
+impl Display for DestructureExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(assign {} {})", self.pattern, self.value)
     }
 }
 
@@ -70,6 +109,16 @@ impl Display for LogicalExpr {
     }
 }
 
+impl Display for ConditionalExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "(?: {} {} {})",
+            self.condition, self.then_branch, self.else_branch
+        )
+    }
+}
+
 impl Display for CallExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let arg_string = self
@@ -84,17 +133,32 @@ impl Display for CallExpr {
 
 impl Display for GetExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "(get-property {} {})", self.object, self.name.lexeme)
+        if self.optional {
+            write!(
+                f,
+                "(optional-get-property {} {})",
+                self.object, self.name.lexeme
+            )
+        } else {
+            write!(f, "(get-property {} {})", self.object, self.name.lexeme)
+        }
     }
 }
 
 impl Display for SetExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "(set-property {} {} {})",
-            self.object, self.name.lexeme, self.value
-        )
+        match self.compound {
+            Some(ref c) => write!(
+                f,
+                "(set-property{} {} {} {})",
+                c, self.object, self.name.lexeme, self.value
+            ),
+            None => write!(
+                f,
+                "(set-property {} {} {})",
+                self.object, self.name.lexeme, self.value
+            ),
+        }
     }
 }
 
@@ -109,3 +173,67 @@ impl Display for SuperExpr {
         write!(f, "(super)")
     }
 }
+
+impl Display for FunctionExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.declaration.borrow())
+    }
+}
+
+impl Display for ListExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let element_string = self
+            .elements
+            .iter()
+            .fold(String::new(), |acc, e| acc + &e.to_string() + " ");
+        let element_string = element_string.trim_end();
+
+        write!(f, "(list {})", element_string)
+    }
+}
+
+impl Display for MapExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entry_string = self.entries.iter().fold(String::new(), |acc, (k, v)| {
+            format!("{}({} {}) ", acc, k, v)
+        });
+        let entry_string = entry_string.trim_end();
+
+        write!(f, "(map {})", entry_string)
+    }
+}
+
+impl Display for IndexExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(get-index {} {})", self.object, self.index)
+    }
+}
+
+impl Display for IndexSetExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.compound {
+            Some(ref c) => write!(
+                f,
+                "(set-index{} {} {} {})",
+                c, self.object, self.index, self.value
+            ),
+            None => write!(
+                f,
+                "(set-index {} {} {})",
+                self.object, self.index, self.value
+            ),
+        }
+    }
+}
+
+impl Display for InterpolationExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let part_string = self
+            .parts
+            .iter()
+            .fold(String::new(), |acc, p| acc + &p.to_string() + " ");
+        let part_string = part_string.trim_end();
+
+        write!(f, "(interpolate {})", part_string)
+    }
+}
@@ -1,22 +1,40 @@
 use crate::environment::EnvironmentTree;
 use crate::expr::*;
+use crate::function::Arity;
+use crate::function::LoxFunction;
+use crate::generator;
 use crate::literal::Literal;
+use crate::literal::NestedGuard;
+use crate::map::LoxMap;
+use crate::map::MapKey;
+use crate::primitive;
 use crate::token::Token;
 use crate::token::TokenType;
 use std::borrow::Borrow;
+use std::cell::RefCell;
 use std::error::Error;
 use std::fmt;
 use std::io::Write;
 use std::rc::Rc;
 
+// msg doesn't include the line, which is kept apart so that
+// caught errors can expose both
+// module is the module of the innermost function the error was raised in,
+// which is only known once the error leaves that function (in_function)
+// thrown is set when the error is a value thrown by lox code,
+// so that it can be caught as is
 #[derive(Debug)]
 pub struct RuntimeError {
     pub msg: String,
+    pub line: Option<usize>,
+    pub module: Option<String>,
+    in_function: bool,
+    pub thrown: Option<Rc<Literal>>,
 }
 
 impl fmt::Display for RuntimeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "RuntimeError: {}", self.msg)
+        write!(f, "RuntimeError: {}", self.located_msg())
     }
 }
 
@@ -24,8 +42,55 @@ impl Error for RuntimeError {}
 
 impl RuntimeError {
     pub fn new(t: &Token, msg: &str) -> RuntimeError {
-        let full_msg = format!("[line {}] {}", t.line, msg);
-        RuntimeError { msg: full_msg }
+        RuntimeError {
+            msg: msg.to_string(),
+            line: Some(t.line),
+            module: None,
+            in_function: false,
+            thrown: None,
+        }
+    }
+
+    pub fn without_line(msg: String) -> RuntimeError {
+        RuntimeError {
+            msg,
+            line: None,
+            module: None,
+            in_function: false,
+            thrown: None,
+        }
+    }
+
+    pub fn throw(t: &Token, value: Rc<Literal>) -> RuntimeError {
+        RuntimeError {
+            msg: format!("Uncaught exception: {}", value),
+            line: Some(t.line),
+            module: None,
+            in_function: false,
+            thrown: Some(value),
+        }
+    }
+
+    // the message prefixed with the line and the module, if known
+    pub fn located_msg(&self) -> String {
+        match (self.line, &self.module) {
+            (Some(line), Some(module)) => {
+                format!("[line {} in module '{}'] {}", line, module, self.msg)
+            }
+            (Some(line), None) => format!("[line {}] {}", line, self.msg),
+            (None, Some(module)) => format!("[in module '{}'] {}", module, self.msg),
+            (None, None) => self.msg.clone(),
+        }
+    }
+
+    // called as the error leaves a function, which only records
+    // the module of the innermost one
+    pub fn within_function(mut self, module: Option<&str>) -> Self {
+        if !self.in_function {
+            self.in_function = true;
+            self.module = module.map(str::to_string);
+        }
+        self
     }
 }
 
@@ -47,6 +112,29 @@ impl Expr {
             Expr::Set(expr) => expr.eval(env, output),
             Expr::This(expr) => expr.eval(env, output),
             Expr::Super(expr) => expr.eval(env, output),
+            Expr::Function(expr) => expr.eval(env, output),
+            Expr::List(expr) => expr.eval(env, output),
+            Expr::Map(expr) => expr.eval(env, output),
+            Expr::Index(expr) => expr.eval(env, output),
+            Expr::IndexSet(expr) => expr.eval(env, output),
+            Expr::Interpolation(expr) => expr.eval(env, output),
+            Expr::Conditional(expr) => expr.eval(env, output),
+            Expr::Destructure(expr) => expr.eval(env, output),
+        }
+    }
+
+    // a link in a chain of calls, property accesses and indexing,
+    // which is None once a '?.' has short-circuited the rest of the chain
+    fn eval_link<T: Write>(
+        &self,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Option<Rc<Literal>>> {
+        match self {
+            Expr::Call(expr) => expr.eval_link(env, output),
+            Expr::Get(expr) => expr.eval_link(env, output),
+            Expr::Index(expr) => expr.eval_link(env, output),
+            _ => self.eval(env, output).map(Some),
         }
     }
 }
@@ -79,16 +167,21 @@ impl UnaryExpr {
         let rhs = self.right.eval(env, output)?;
         match self.operator.token_type {
             TokenType::BANG => Ok(Rc::new(Literal::BoolLiteral(rhs.is_truthy()))),
-            TokenType::MINUS => match rhs.negative() {
-                Ok(x) => Ok(Rc::new(x)),
-                _ => Err(RuntimeError::new(
-                    &self.operator,
-                    &format!(
-                        "{} cannot be applied to {}, it must be a number",
-                        self.operator.lexeme, rhs
-                    ),
-                )),
-            },
+            TokenType::MINUS => {
+                if let Some(result) = call_operator(&rhs, "__neg__", vec![], env, output) {
+                    return result;
+                }
+                match rhs.negative() {
+                    Ok(x) => Ok(Rc::new(x)),
+                    _ => Err(RuntimeError::new(
+                        &self.operator,
+                        &format!(
+                            "{} cannot be applied to {}, it must be a number",
+                            self.operator.lexeme, rhs
+                        ),
+                    )),
+                }
+            }
             ref tt => Err(RuntimeError::new(
                 &self.operator,
                 &format!("{:?} is unimplemented", tt),
@@ -101,97 +194,285 @@ impl BinaryExpr {
     pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
         let lhs = self.left.eval(env, output)?;
         let rhs = self.right.eval(env, output)?;
-        match self.operator.token_type {
-            TokenType::MINUS => match lhs.minus(&rhs) {
+        Self::operate(
+            &self.operator,
+            self.operator.token_type,
+            &lhs,
+            &rhs,
+            env,
+            output,
+        )
+    }
+
+    // token_type might differ from the one of operator (e.g. PLUS for '+=')
+    // while operator is only used for reporting errors
+    pub fn operate<T: Write>(
+        operator: &Token,
+        token_type: TokenType,
+        lhs: &Rc<Literal>,
+        rhs: &Rc<Literal>,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Rc<Literal>> {
+        if let Some(result) = operator_method(token_type)
+            .and_then(|name| call_operator(lhs, name, vec![rhs.clone()], env, output))
+        {
+            return result;
+        }
+        // 1 + v calls v.__radd__(1), and 1 < v is v > 1
+        if let Some(result) = reflected_operator_method(token_type)
+            .and_then(|name| call_operator(rhs, name, vec![lhs.clone()], env, output))
+        {
+            return result;
+        }
+        match token_type {
+            TokenType::MINUS => match lhs.minus(rhs) {
                 Ok(x) => Ok(Rc::new(x)),
                 _ => Err(RuntimeError::new(
-                    &self.operator,
+                    operator,
                     &format!(
                         "{} cannot be applied to {} and {}, both must be number",
-                        self.operator.lexeme, lhs, rhs
+                        operator.lexeme, lhs, rhs
                     ),
                 )),
             },
-            TokenType::PLUS => match lhs.plus(&rhs) {
+            TokenType::PLUS => match lhs.plus(rhs) {
                 Ok(x) => Ok(Rc::new(x)),
                 _ => Err(RuntimeError::new(
-                    &self.operator,
+                    operator,
                     &format!(
                         "{} cannot be applied to {}, must be two numbers or two strings",
-                        self.operator.lexeme, rhs
+                        operator.lexeme, rhs
                     ),
                 )),
             },
-            TokenType::STAR => match lhs.multiply(&rhs) {
+            TokenType::STAR => match lhs.multiply(rhs) {
                 Ok(x) => Ok(Rc::new(x)),
                 _ => Err(RuntimeError::new(
-                    &self.operator,
+                    operator,
                     &format!(
                         "{} cannot be applied to {} and {}, both must be number",
-                        self.operator.lexeme, lhs, rhs
+                        operator.lexeme, lhs, rhs
                     ),
                 )),
             },
-            TokenType::SLASH => match rhs.divide(&lhs) {
+            TokenType::SLASH => match rhs.divide(lhs) {
                 Ok(x) => Ok(Rc::new(x)),
                 _ => Err(RuntimeError::new(
-                    &self.operator,
+                    operator,
                     &format!(
                         "{} cannot be applied to {} and {}, both must be number",
-                        self.operator.lexeme, lhs, rhs
+                        operator.lexeme, lhs, rhs
                     ),
                 )),
             },
-            TokenType::EQUAL_EQUAL => Ok(Rc::new(lhs.equal(&rhs))),
-            TokenType::BANG_EQUAL => Ok(Rc::new(lhs.not_equal(&rhs))),
-            TokenType::GREATER => match lhs.greater(&rhs) {
+            TokenType::PERCENT => match lhs.modulo(rhs) {
                 Ok(x) => Ok(Rc::new(x)),
                 _ => Err(RuntimeError::new(
-                    &self.operator,
+                    operator,
                     &format!(
                         "{} cannot be applied to {} and {}, both must be number",
-                        self.operator.lexeme, lhs, rhs
+                        operator.lexeme, lhs, rhs
                     ),
                 )),
             },
-            TokenType::GREATER_EQUAL => match lhs.greater_equal(&rhs) {
+            TokenType::SLASH_SLASH => match lhs.floor_divide(rhs) {
                 Ok(x) => Ok(Rc::new(x)),
                 _ => Err(RuntimeError::new(
-                    &self.operator,
+                    operator,
                     &format!(
                         "{} cannot be applied to {} and {}, both must be number",
-                        self.operator.lexeme, lhs, rhs
+                        operator.lexeme, lhs, rhs
                     ),
                 )),
             },
-            TokenType::LESS => match lhs.less(&rhs) {
+            TokenType::STAR_STAR => match lhs.power(rhs) {
                 Ok(x) => Ok(Rc::new(x)),
                 _ => Err(RuntimeError::new(
-                    &self.operator,
+                    operator,
                     &format!(
                         "{} cannot be applied to {} and {}, both must be number",
-                        self.operator.lexeme, lhs, rhs
+                        operator.lexeme, lhs, rhs
                     ),
                 )),
             },
-            TokenType::LESS_EQUAL => match lhs.less_equal(&rhs) {
+            TokenType::EQUAL_EQUAL => {
+                let is_equal = equal(lhs, rhs, env, output)?;
+                Ok(Rc::new(Literal::BoolLiteral(is_equal)))
+            }
+            TokenType::BANG_EQUAL => {
+                let is_equal = equal(lhs, rhs, env, output)?;
+                Ok(Rc::new(Literal::BoolLiteral(!is_equal)))
+            }
+            TokenType::GREATER => match lhs.greater(rhs) {
                 Ok(x) => Ok(Rc::new(x)),
                 _ => Err(RuntimeError::new(
-                    &self.operator,
+                    operator,
                     &format!(
                         "{} cannot be applied to {} and {}, both must be number",
-                        self.operator.lexeme, lhs, rhs
+                        operator.lexeme, lhs, rhs
                     ),
                 )),
             },
-            ref tt => Err(RuntimeError::new(
-                &self.operator,
+            TokenType::GREATER_EQUAL => match lhs.greater_equal(rhs) {
+                Ok(x) => Ok(Rc::new(x)),
+                _ => Err(RuntimeError::new(
+                    operator,
+                    &format!(
+                        "{} cannot be applied to {} and {}, both must be number",
+                        operator.lexeme, lhs, rhs
+                    ),
+                )),
+            },
+            TokenType::LESS => match lhs.less(rhs) {
+                Ok(x) => Ok(Rc::new(x)),
+                _ => Err(RuntimeError::new(
+                    operator,
+                    &format!(
+                        "{} cannot be applied to {} and {}, both must be number",
+                        operator.lexeme, lhs, rhs
+                    ),
+                )),
+            },
+            TokenType::LESS_EQUAL => match lhs.less_equal(rhs) {
+                Ok(x) => Ok(Rc::new(x)),
+                _ => Err(RuntimeError::new(
+                    operator,
+                    &format!(
+                        "{} cannot be applied to {} and {}, both must be number",
+                        operator.lexeme, lhs, rhs
+                    ),
+                )),
+            },
+            tt => Err(RuntimeError::new(
+                operator,
                 &format!("{:?} is unimplemented", tt),
             )),
         }
     }
 }
 
+// the method that overloads a binary operator, except for == and !=
+// which both go through equal()
+fn operator_method(token_type: TokenType) -> Option<&'static str> {
+    match token_type {
+        TokenType::PLUS => Some("__add__"),
+        TokenType::MINUS => Some("__sub__"),
+        TokenType::STAR => Some("__mul__"),
+        TokenType::SLASH => Some("__div__"),
+        TokenType::PERCENT => Some("__mod__"),
+        TokenType::SLASH_SLASH => Some("__floordiv__"),
+        TokenType::STAR_STAR => Some("__pow__"),
+        TokenType::GREATER => Some("__gt__"),
+        TokenType::GREATER_EQUAL => Some("__ge__"),
+        TokenType::LESS => Some("__lt__"),
+        TokenType::LESS_EQUAL => Some("__le__"),
+        _ => None,
+    }
+}
+
+// the method of the right operand that is called with the left one when
+// the left one has none, comparisons are mirrored while the others have
+// methods of their own since their operands can't be swapped
+fn reflected_operator_method(token_type: TokenType) -> Option<&'static str> {
+    match token_type {
+        TokenType::PLUS => Some("__radd__"),
+        TokenType::MINUS => Some("__rsub__"),
+        TokenType::STAR => Some("__rmul__"),
+        TokenType::SLASH => Some("__rdiv__"),
+        TokenType::PERCENT => Some("__rmod__"),
+        TokenType::SLASH_SLASH => Some("__rfloordiv__"),
+        TokenType::STAR_STAR => Some("__rpow__"),
+        TokenType::GREATER => Some("__lt__"),
+        TokenType::GREATER_EQUAL => Some("__le__"),
+        TokenType::LESS => Some("__gt__"),
+        TokenType::LESS_EQUAL => Some("__ge__"),
+        _ => None,
+    }
+}
+
+// calls the method overloading an operator if value is an instance whose
+// class (or a superclass) defines it, the resolver has already made sure
+// that the method accepts args
+fn call_operator<T: Write>(
+    value: &Rc<Literal>,
+    name: &str,
+    args: Vec<Rc<Literal>>,
+    env: &mut EnvironmentTree,
+    output: &mut T,
+) -> Option<Result<Rc<Literal>>> {
+    let Literal::InstanceLiteral(ref instance) = **value else {
+        return None;
+    };
+    let method = instance.borrow_mut().get_method(name, env, value)?;
+    let Literal::FunctionLiteral(ref fun) = *method else {
+        unreachable!("Method must be a function")
+    };
+    Some(fun.call(args, env, output))
+}
+
+// instances are compared with __eq__ if either of them defines it
+pub fn equal<T: Write>(
+    lhs: &Rc<Literal>,
+    rhs: &Rc<Literal>,
+    env: &mut EnvironmentTree,
+    output: &mut T,
+) -> Result<bool> {
+    let result = call_operator(lhs, "__eq__", vec![rhs.clone()], env, output)
+        .or_else(|| call_operator(rhs, "__eq__", vec![lhs.clone()], env, output));
+    match result {
+        Some(result) => Ok(result?.is_truthy()),
+        None => Ok(lhs.equal(rhs).is_truthy()),
+    }
+}
+
+// the way print and interpolation show a value, which is
+// up to __str__ for instances that define it, even within collections
+pub fn stringify<T: Write>(
+    value: &Rc<Literal>,
+    env: &mut EnvironmentTree,
+    output: &mut T,
+) -> Result<String> {
+    match **value {
+        // elements are copied first, since __str__ might change the collection
+        Literal::ListLiteral(ref l) => {
+            let Some(_guard) = NestedGuard::enter(value) else {
+                return Ok("[...]".to_string());
+            };
+            let elements = l.borrow().clone();
+            let elements = elements
+                .iter()
+                .map(|e| stringify(e, env, output))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(format!("[{}]", elements.join(", ")))
+        }
+        Literal::MapLiteral(ref m) => {
+            let Some(_guard) = NestedGuard::enter(value) else {
+                return Ok("{...}".to_string());
+            };
+            let entries: Vec<_> = {
+                let m = m.borrow();
+                m.keys().cloned().zip(m.values().cloned()).collect()
+            };
+            let entries = entries
+                .iter()
+                .map(|(k, v)| {
+                    Ok(format!(
+                        "{}: {}",
+                        k.to_literal(),
+                        stringify(v, env, output)?
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(format!("{{{}}}", entries.join(", ")))
+        }
+        _ => match call_operator(value, "__str__", vec![], env, output) {
+            Some(result) => Ok(result?.to_string()),
+            None => Ok(value.to_string()),
+        },
+    }
+}
+
 impl VarExpr {
     pub fn eval<T: Write>(
         &self,
@@ -202,18 +483,118 @@ impl VarExpr {
     }
 }
 
+impl CompoundOp {
+    // returns the new value to be assigned and the value of the whole expression
+    pub fn apply<T: Write>(
+        &self,
+        current: Rc<Literal>,
+        value: &Rc<Literal>,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<(Rc<Literal>, Rc<Literal>)> {
+        let token_type = match self.operator.token_type {
+            TokenType::PLUS_EQUAL | TokenType::PLUS_PLUS => TokenType::PLUS,
+            TokenType::MINUS_EQUAL | TokenType::MINUS_MINUS => TokenType::MINUS,
+            TokenType::STAR_EQUAL => TokenType::STAR,
+            TokenType::SLASH_EQUAL => TokenType::SLASH,
+            tt => unreachable!("{:?} is not a compound assignment operator", tt),
+        };
+        let new = BinaryExpr::operate(&self.operator, token_type, &current, value, env, output)?;
+        if self.postfix {
+            Ok((new, current))
+        } else {
+            Ok((new.clone(), new))
+        }
+    }
+}
+
 impl AssignExpr {
+    pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
+        // the current value is read first, as in a = a + b
+        let (value, result) = match self.compound {
+            Some(ref c) => {
+                let current = env.get(&self.name, self.scope_offset)?.clone();
+                let value = self.value.eval(env, output)?;
+                c.apply(current, &value, env, output)?
+            }
+            None => {
+                let value = self.value.eval(env, output)?;
+                (value.clone(), value)
+            }
+        };
+        env.assign(&self.name, value, self.scope_offset)?;
+        Ok(result)
+    }
+}
+
+impl DestructureExpr {
     pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
         let value = self.value.eval(env, output)?;
-        env.assign(&self.name, value.clone(), self.scope_offset)?;
+        let values = self.pattern.destructure(&value, env, output)?;
+        for ((name, offset), v) in self
+            .pattern
+            .names
+            .iter()
+            .zip(&self.scope_offsets)
+            .zip(values)
+        {
+            env.assign(name, v, *offset)?;
+        }
         Ok(value)
     }
 }
 
+impl Destructuring {
+    // the values for each of the names, all of them are read before any
+    // is bound so that [a, b] = [b, a] swaps them
+    pub fn destructure<T: Write>(
+        &self,
+        value: &Rc<Literal>,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Vec<Rc<Literal>>> {
+        if self.bracket.token_type == TokenType::LEFT_BRACKET {
+            let Literal::ListLiteral(ref list) = **value else {
+                return Err(RuntimeError::new(
+                    &self.bracket,
+                    &format!("Can only destructure a list with '[', got {}.", value),
+                ));
+            };
+            let list = list.borrow();
+            if list.len() < self.names.len() {
+                let msg = format!(
+                    "Expected at least {} elements to destructure but got {}.",
+                    self.names.len(),
+                    list.len()
+                );
+                return Err(RuntimeError::new(&self.bracket, &msg));
+            }
+            Ok(list[..self.names.len()].to_vec())
+        } else {
+            let Literal::InstanceLiteral(ref instance) = **value else {
+                return Err(RuntimeError::new(
+                    &self.bracket,
+                    &format!("Can only destructure an instance with '{{', got {}.", value),
+                ));
+            };
+            let mut values = vec![];
+            for name in &self.names {
+                let field = instance.borrow_mut().get(name, env, value)?;
+                values.push(call_getter(field, env, output)?);
+            }
+            Ok(values)
+        }
+    }
+}
+
 impl LogicalExpr {
     pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
         let lhs = self.left.eval(env, output)?;
-        if self.operator.token_type == TokenType::OR {
+        if self.operator.token_type == TokenType::QUESTION_QUESTION {
+            if *lhs != Literal::Empty {
+                return Ok(lhs);
+            }
+        } else if self.operator.token_type == TokenType::OR {
             if lhs.is_truthy() {
                 return Ok(lhs);
             }
@@ -224,9 +605,39 @@ impl LogicalExpr {
     }
 }
 
+impl ConditionalExpr {
+    pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
+        if self.condition.eval(env, output)?.is_truthy() {
+            self.then_branch.eval(env, output)
+        } else {
+            self.else_branch.eval(env, output)
+        }
+    }
+}
+
 impl CallExpr {
     pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
-        let callee = self.callee.eval(env, output)?;
+        let value = self.eval_link(env, output)?;
+        Ok(value.unwrap_or_else(|| Rc::new(Literal::Empty)))
+    }
+
+    fn eval_link<T: Write>(
+        &self,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Option<Rc<Literal>>> {
+        match self.callee.eval_link(env, output)? {
+            Some(callee) => self.call(callee, env, output).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn call<T: Write>(
+        &self,
+        callee: Rc<Literal>,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Rc<Literal>> {
         let mut args = vec![];
         for arg in &self.args {
             args.push(arg.eval(env, output)?);
@@ -243,7 +654,15 @@ impl CallExpr {
             }
             Literal::NativeFunctionLiteral(fun) => {
                 self.check_arity(args.len(), fun.arity())?;
-                Ok(fun.call(args)?)
+                Ok(fun.call(args, &self.paren)?)
+            }
+            Literal::VariantLiteral(variant) => {
+                self.check_arity(args.len(), variant.arity())?;
+                Ok(variant.call(args))
+            }
+            Literal::NativeMethodLiteral(method) => {
+                self.check_arity(args.len(), method.arity())?;
+                method.call(args, &self.paren, env, output)
             }
             _ => Err(RuntimeError::new(
                 &self.paren,
@@ -252,13 +671,13 @@ impl CallExpr {
         }
     }
 
-    fn check_arity(&self, args_len: usize, arity: usize) -> Result<()> {
-        if args_len == arity {
+    fn check_arity(&self, args_len: usize, arity: Arity) -> Result<()> {
+        if arity.accepts(args_len) {
             Ok(())
         } else {
             Err(RuntimeError::new(
                 &self.paren,
-                &format!("Expected {} arguments but got {}. ", arity, args_len),
+                &format!("Expected {} arguments but got {}.", arity, args_len),
             ))
         }
     }
@@ -266,27 +685,128 @@ impl CallExpr {
 
 impl GetExpr {
     pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
-        let object = self.object.eval(env, output)?;
-        if let Literal::InstanceLiteral(instance) = object.borrow() {
-            instance.borrow_mut().get(&self.name, env, &object)
-        } else {
-            Err(RuntimeError::new(
+        let value = self.eval_link(env, output)?;
+        Ok(value.unwrap_or_else(|| Rc::new(Literal::Empty)))
+    }
+
+    fn eval_link<T: Write>(
+        &self,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Option<Rc<Literal>>> {
+        match self.object.eval_link(env, output)? {
+            Some(object) if self.optional && *object == Literal::Empty => Ok(None),
+            Some(object) => self.get(object, env, output).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn get<T: Write>(
+        &self,
+        object: Rc<Literal>,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Rc<Literal>> {
+        match object.borrow() {
+            Literal::InstanceLiteral(instance) => {
+                let value = instance.borrow_mut().get(&self.name, env, &object)?;
+                call_getter(value, env, output)
+            }
+            Literal::ClassLiteral(cls) => match cls.find_static_method(&self.name.lexeme) {
+                Some(m) => Ok(Rc::new(Literal::FunctionLiteral(m.clone()))),
+                None => Err(RuntimeError::new(
+                    &self.name,
+                    &format!("Undefined static method '{}'.", &self.name.lexeme),
+                )),
+            },
+            Literal::GeneratorLiteral(_) => generator::get_method(&object, &self.name),
+            Literal::StringLiteral(_) | Literal::NumberLiteral(_) => {
+                primitive::get_method(&object, &self.name)
+            }
+            Literal::EnumLiteral(ref e) => e.get(&self.name),
+            Literal::EnumValueLiteral(ref v) => v.get(&self.name),
+            Literal::ModuleLiteral(module) => match module.get(&self.name.lexeme, env) {
+                Some(value) => Ok(value.clone()),
+                None => Err(RuntimeError::new(
+                    &self.name,
+                    &format!(
+                        "Module '{}' has no export '{}'.",
+                        module.name(),
+                        &self.name.lexeme
+                    ),
+                )),
+            },
+            _ => Err(RuntimeError::new(
                 &self.name,
-                "Only instances have properties.",
-            ))
+                "Only instances, classes and modules have properties.",
+            )),
         }
     }
 }
 
+// calls a method that the interpreter itself relies on (e.g. iterator())
+pub fn call_method<T: Write>(
+    object: &Rc<Literal>,
+    name: &Token,
+    args: Vec<Rc<Literal>>,
+    env: &mut EnvironmentTree,
+    output: &mut T,
+) -> Result<Rc<Literal>> {
+    let Literal::InstanceLiteral(ref instance) = **object else {
+        return Err(RuntimeError::new(
+            name,
+            &format!("Can't call '{}' on {}.", name.lexeme, object),
+        ));
+    };
+    let method = instance.borrow_mut().get(name, env, object)?;
+    match *method {
+        Literal::FunctionLiteral(ref fun) if fun.arity().accepts(args.len()) => {
+            fun.call(args, env, output)
+        }
+        _ => Err(RuntimeError::new(
+            name,
+            &format!(
+                "'{}' must be a method that takes {} arguments.",
+                name.lexeme,
+                args.len()
+            ),
+        )),
+    }
+}
+
+// getters are called as soon as they are accessed, which has to happen
+// after the instance is no longer borrowed since they might access 'this'
+fn call_getter<T: Write>(
+    value: Rc<Literal>,
+    env: &mut EnvironmentTree,
+    output: &mut T,
+) -> Result<Rc<Literal>> {
+    match *value {
+        Literal::FunctionLiteral(ref fun) if fun.is_getter() => fun.call(vec![], env, output),
+        _ => Ok(value),
+    }
+}
+
 impl SetExpr {
     pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
         let object = self.object.eval(env, output)?;
         if let Literal::InstanceLiteral(instance) = object.borrow() {
-            let value = self.value.eval(env, output)?;
-            instance
-                .borrow_mut()
-                .set(self.name.lexeme.clone(), value.clone());
-            Ok(value)
+            // the current value is read first and the same way as a.b does,
+            // so a getter is called
+            let (value, result) = match self.compound {
+                Some(ref c) => {
+                    let current = instance.borrow_mut().get(&self.name, env, &object)?;
+                    let current = call_getter(current, env, output)?;
+                    let value = self.value.eval(env, output)?;
+                    c.apply(current, &value, env, output)?
+                }
+                None => {
+                    let value = self.value.eval(env, output)?;
+                    (value.clone(), value)
+                }
+            };
+            instance.borrow_mut().set(self.name.lexeme.clone(), value);
+            Ok(result)
         } else {
             Err(RuntimeError::new(&self.name, "Only instances have fields."))
         }
@@ -304,11 +824,7 @@ impl ThisExpr {
 }
 
 impl SuperExpr {
-    pub fn eval<T: Write>(
-        &self,
-        env: &mut EnvironmentTree,
-        _output: &mut T,
-    ) -> Result<Rc<Literal>> {
+    pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
         // kinda hacky
         let offset = self.scope_offset.expect("Unresolved 'super'");
         let Literal::ClassLiteral(ref superclass) = *(env
@@ -324,11 +840,184 @@ impl SuperExpr {
             .clone();
 
         if let Literal::InstanceLiteral(ref instance) = *(this_literal) {
-            instance
-                .borrow_mut()
-                .get_super_method(superclass, &self.method, env, &this_literal)
+            let method = instance.borrow_mut().get_super_method(
+                superclass,
+                &self.method,
+                env,
+                &this_literal,
+            )?;
+            call_getter(method, env, output)
         } else {
             panic!("'this' is not an instance")
         }
     }
 }
+
+impl FunctionExpr {
+    pub fn eval<T: Write>(
+        &self,
+        env: &mut EnvironmentTree,
+        _output: &mut T,
+    ) -> Result<Rc<Literal>> {
+        let cur_env = env.keep_branch();
+        let fun = LoxFunction::new(self.declaration.clone(), cur_env, false);
+        Ok(Rc::new(Literal::FunctionLiteral(fun)))
+    }
+}
+
+impl ListExpr {
+    pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
+        let mut elements = vec![];
+        for e in &self.elements {
+            elements.push(e.eval(env, output)?);
+        }
+        Ok(Rc::new(Literal::ListLiteral(RefCell::new(elements))))
+    }
+}
+
+impl MapExpr {
+    pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
+        let mut map = LoxMap::new();
+        for (k, v) in &self.entries {
+            let key = map_key(&self.brace, &*k.eval(env, output)?)?;
+            map.insert(key, v.eval(env, output)?);
+        }
+        Ok(Rc::new(Literal::MapLiteral(RefCell::new(map))))
+    }
+}
+
+pub fn map_key(token: &Token, key: &Literal) -> Result<MapKey> {
+    MapKey::from_literal(key).ok_or_else(|| {
+        RuntimeError::new(
+            token,
+            &format!("Map key must be a string, number or boolean, got {}.", key),
+        )
+    })
+}
+
+// converts a lox value into a valid index of a list with the given length
+fn list_index(bracket: &Token, index: &Literal, len: usize) -> Result<usize> {
+    match *index {
+        Literal::NumberLiteral(n) if n.fract() == 0.0 => {
+            if n >= 0.0 && n < len as f64 {
+                Ok(n as usize)
+            } else {
+                Err(RuntimeError::new(
+                    bracket,
+                    &format!("Index {} is out of range for a list of length {}.", n, len),
+                ))
+            }
+        }
+        _ => Err(RuntimeError::new(
+            bracket,
+            &format!("List index must be an integer, got {}.", index),
+        )),
+    }
+}
+
+impl IndexExpr {
+    pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
+        let value = self.eval_link(env, output)?;
+        Ok(value.unwrap_or_else(|| Rc::new(Literal::Empty)))
+    }
+
+    fn eval_link<T: Write>(
+        &self,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Option<Rc<Literal>>> {
+        match self.object.eval_link(env, output)? {
+            Some(object) => self.get(object, env, output).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn get<T: Write>(
+        &self,
+        object: Rc<Literal>,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Rc<Literal>> {
+        let index = self.index.eval(env, output)?;
+        match *object {
+            Literal::ListLiteral(ref list) => {
+                let list = list.borrow();
+                let i = list_index(&self.bracket, &index, list.len())?;
+                Ok(list[i].clone())
+            }
+            Literal::MapLiteral(ref map) => {
+                let key = map_key(&self.bracket, &index)?;
+                map.borrow().get(&key).cloned().ok_or_else(|| {
+                    RuntimeError::new(&self.bracket, &format!("Undefined key '{}'.", index))
+                })
+            }
+            _ => Err(RuntimeError::new(
+                &self.bracket,
+                "Only lists and maps can be indexed.",
+            )),
+        }
+    }
+}
+
+impl IndexSetExpr {
+    pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
+        let object = self.object.eval(env, output)?;
+        let index = self.index.eval(env, output)?;
+        match *object {
+            Literal::ListLiteral(ref list) => {
+                // the list can't stay borrowed while the value is evaluated or
+                // an overloaded operator runs, so the index is checked again afterwards
+                let (value, result) = match self.compound {
+                    Some(ref c) => {
+                        let i = list_index(&self.bracket, &index, list.borrow().len())?;
+                        let current = list.borrow()[i].clone();
+                        let value = self.value.eval(env, output)?;
+                        c.apply(current, &value, env, output)?
+                    }
+                    None => {
+                        let value = self.value.eval(env, output)?;
+                        (value.clone(), value)
+                    }
+                };
+                let mut list = list.borrow_mut();
+                let i = list_index(&self.bracket, &index, list.len())?;
+                list[i] = value;
+                Ok(result)
+            }
+            Literal::MapLiteral(ref map) => {
+                let key = map_key(&self.bracket, &index)?;
+                let (value, result) = match self.compound {
+                    Some(ref c) => {
+                        let current = map.borrow().get(&key).cloned().ok_or_else(|| {
+                            RuntimeError::new(&self.bracket, &format!("Undefined key '{}'.", index))
+                        })?;
+                        let value = self.value.eval(env, output)?;
+                        c.apply(current, &value, env, output)?
+                    }
+                    None => {
+                        let value = self.value.eval(env, output)?;
+                        (value.clone(), value)
+                    }
+                };
+                map.borrow_mut().insert(key, value);
+                Ok(result)
+            }
+            _ => Err(RuntimeError::new(
+                &self.bracket,
+                "Only lists and maps can be indexed.",
+            )),
+        }
+    }
+}
+
+impl InterpolationExpr {
+    pub fn eval<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<Rc<Literal>> {
+        // every part is stringified the same way as print does
+        let mut value = String::new();
+        for p in &self.parts {
+            let part = p.eval(env, output)?;
+            value += &stringify(&part, env, output)?;
+        }
+        Ok(Rc::new(Literal::StringLiteral(value)))
+    }
+}
@@ -1,5 +1,6 @@
 use crate::expr::*;
 use crate::resolver::ClassType;
+use crate::resolver::FunctionType;
 use crate::resolver::ResolutionError;
 use crate::resolver::Resolver;
 use crate::resolver::Result;
@@ -19,14 +20,22 @@ impl Expr {
             Expr::Set(expr) => expr.resolve(resolver),
             Expr::This(expr) => expr.resolve(resolver),
             Expr::Super(expr) => expr.resolve(resolver),
+            Expr::Function(expr) => expr.resolve(resolver),
+            Expr::List(expr) => expr.resolve(resolver),
+            Expr::Map(expr) => expr.resolve(resolver),
+            Expr::Index(expr) => expr.resolve(resolver),
+            Expr::IndexSet(expr) => expr.resolve(resolver),
+            Expr::Interpolation(expr) => expr.resolve(resolver),
+            Expr::Conditional(expr) => expr.resolve(resolver),
+            Expr::Destructure(expr) => expr.resolve(resolver),
         }
     }
 }
 
 impl VarExpr {
     pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
-        if let Some(&b) = resolver.get(&self.name) {
-            if !b {
+        if let Some(v) = resolver.get(&self.name) {
+            if !v.defined {
                 return Err(ResolutionError::new(
                     &self.name,
                     "Can't read local variable in its own initializer.",
@@ -42,7 +51,20 @@ impl VarExpr {
 impl AssignExpr {
     pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
         self.value.resolve(resolver)?;
-        self.scope_offset = resolver.resolve_local(&self.name);
+        self.scope_offset = resolver.resolve_assignment(&self.name)?;
+        Ok(())
+    }
+}
+
+impl DestructureExpr {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        self.value.resolve(resolver)?;
+        self.scope_offsets = self
+            .pattern
+            .names
+            .iter()
+            .map(|name| resolver.resolve_assignment(name))
+            .collect::<Result<_>>()?;
         Ok(())
     }
 }
@@ -89,6 +111,14 @@ impl LogicalExpr {
     }
 }
 
+impl ConditionalExpr {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        self.condition.resolve(resolver)?;
+        self.then_branch.resolve(resolver)?;
+        self.else_branch.resolve(resolver)
+    }
+}
+
 impl GetExpr {
     pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
         self.object.resolve(resolver)
@@ -109,6 +139,11 @@ impl ThisExpr {
                 &self.keyword,
                 "Can't use 'this' outside of a class.",
             ));
+        } else if resolver.current_cls == ClassType::Static {
+            return Err(ResolutionError::new(
+                &self.keyword,
+                "Can't use 'this' in a static method.",
+            ));
         }
         self.scope_offset = resolver.resolve_local(&self.keyword);
         Ok(())
@@ -122,6 +157,16 @@ impl SuperExpr {
                 &self.keyword,
                 "Can't use 'super' outside of a class.",
             ));
+        } else if resolver.current_cls == ClassType::Static {
+            return Err(ResolutionError::new(
+                &self.keyword,
+                "Can't use 'super' in a static method.",
+            ));
+        } else if resolver.current_cls == ClassType::Trait {
+            return Err(ResolutionError::new(
+                &self.keyword,
+                "Can't use 'super' in a trait.",
+            ));
         } else if resolver.current_cls != ClassType::Subclass {
             return Err(ResolutionError::new(
                 &self.keyword,
@@ -133,3 +178,57 @@ impl SuperExpr {
         Ok(())
     }
 }
+
+impl FunctionExpr {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        let fun_type = if self.declaration.borrow().is_generator {
+            FunctionType::Generator
+        } else {
+            FunctionType::Fun
+        };
+        self.declaration.borrow_mut().resolve_fn(resolver, fun_type)
+    }
+}
+
+impl ListExpr {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        for e in self.elements.iter_mut() {
+            e.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl MapExpr {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        for (k, v) in self.entries.iter_mut() {
+            k.resolve(resolver)?;
+            v.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl IndexExpr {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        self.object.resolve(resolver)?;
+        self.index.resolve(resolver)
+    }
+}
+
+impl IndexSetExpr {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        self.object.resolve(resolver)?;
+        self.index.resolve(resolver)?;
+        self.value.resolve(resolver)
+    }
+}
+
+impl InterpolationExpr {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        for p in self.parts.iter_mut() {
+            p.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
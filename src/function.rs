@@ -1,6 +1,7 @@
 use crate::environment::Environment;
 use crate::environment::EnvironmentTree;
 use crate::expr_interpret::Result;
+use crate::generator::LoxGenerator;
 use crate::literal::Literal;
 use crate::statement::FunctionStmt;
 use crate::stmt_interpret::ExecError;
@@ -10,7 +11,47 @@ use std::fmt::Display;
 use std::io::Write;
 use std::rc::Rc;
 
+// max is None if there is a rest parameter
+#[derive(Clone, Copy)]
+pub struct Arity {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl Display for Arity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "{}", max),
+            Some(max) => write!(f, "{} to {}", self.min, max),
+            None => write!(f, "at least {}", self.min),
+        }
+    }
+}
+
+impl Arity {
+    pub fn exact(n: usize) -> Self {
+        Arity {
+            min: n,
+            max: Some(n),
+        }
+    }
+
+    pub fn accepts(&self, n: usize) -> bool {
+        n >= self.min && self.max.is_none_or(|max| n <= max)
+    }
+}
+
+impl FunctionStmt {
+    pub fn arity(&self) -> Arity {
+        Arity {
+            min: self.params.iter().filter(|p| p.default.is_none()).count(),
+            max: self.rest.is_none().then_some(self.params.len()),
+        }
+    }
+}
+
 // TODO: not sure if a Callable trait would be beneficial?
+#[derive(Clone)]
 pub struct LoxFunction {
     declaration: Rc<RefCell<FunctionStmt>>,
     closure: NodeId,
@@ -48,6 +89,12 @@ impl LoxFunction {
         env: &mut EnvironmentTree,
         output: &mut T,
     ) -> Result<Rc<Literal>> {
+        // the body only runs once the generator is asked for a value
+        if self.declaration.borrow().is_generator {
+            let generator = LoxGenerator::new(self.clone(), args);
+            return Ok(Rc::new(Literal::GeneratorLiteral(Rc::new(generator))));
+        }
+
         let prev = env.checkout(self.closure);
         let mut return_value = Rc::new(Literal::Empty);
         if self.is_initializer {
@@ -55,32 +102,91 @@ impl LoxFunction {
         }
 
         env.push(Environment::new());
-        for (i, p) in self.declaration.borrow().params.iter().enumerate() {
-            env.define(p.lexeme.clone(), args[i].clone());
+        let result = self
+            .define_params(args, env, output)
+            .and_then(|_| self.execute_body(env, output));
+
+        // env has to be restored even if there is an error,
+        // since it might be caught by the caller
+        env.pop();
+        env.checkout(prev);
+
+        let result = result.map_err(|error| error.within_function(self.module(env)));
+        match result? {
+            Some(value) if !self.is_initializer => Ok(value),
+            _ => Ok(return_value),
         }
+    }
 
+    // returns the value of the return statement if there is one
+    fn execute_body<T: Write>(
+        &self,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Option<Rc<Literal>>> {
         for s in self.declaration.borrow().body.iter() {
             match s.execute(env, output) {
                 Ok(_) => {}
-                Err(ExecError::Return(value)) => {
-                    if !self.is_initializer {
-                        return_value = value;
-                    }
-                    break;
+                Err(ExecError::Return(value)) => return Ok(Some(value)),
+                // thrown values are carried by the error itself
+                Err(ExecError::RuntimeError(error)) | Err(ExecError::Throw(error)) => {
+                    return Err(error)
                 }
-                Err(ExecError::RuntimeError(error)) => {
-                    return Err(error);
+                // resolver rejects break and continue outside of loops
+                Err(ExecError::Break) | Err(ExecError::Continue) => {
+                    unreachable!()
                 }
             }
         }
-        env.pop();
-        env.checkout(prev);
+        Ok(None)
+    }
+
+    // defaults are evaluated in the function's env, one param at a time
+    pub fn define_params<T: Write>(
+        &self,
+        args: Vec<Rc<Literal>>,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<()> {
+        let declaration = self.declaration.borrow();
+        let mut args = args.into_iter();
+        for p in &declaration.params {
+            let value = match (args.next(), &p.default) {
+                (Some(arg), _) => arg,
+                (None, Some(default)) => default.eval(env, output)?,
+                (None, None) => unreachable!("Arity is checked before calling"),
+            };
+            env.define(p.name.lexeme.clone(), value);
+        }
+        if let Some(ref rest) = declaration.rest {
+            let rest_args = RefCell::new(args.collect());
+            env.define(
+                rest.lexeme.clone(),
+                Rc::new(Literal::ListLiteral(rest_args)),
+            );
+        }
+        Ok(())
+    }
+
+    pub fn declaration(&self) -> &Rc<RefCell<FunctionStmt>> {
+        &self.declaration
+    }
+
+    pub fn closure(&self) -> NodeId {
+        self.closure
+    }
+
+    // the module the function is defined in, None for the main file
+    pub fn module<'a>(&self, env: &'a EnvironmentTree) -> Option<&'a str> {
+        env.module_of(self.closure)
+    }
 
-        Ok(return_value)
+    pub fn is_getter(&self) -> bool {
+        self.declaration.borrow().is_getter
     }
 
-    pub fn arity(&self) -> usize {
-        self.declaration.borrow().params.len()
+    pub fn arity(&self) -> Arity {
+        self.declaration.borrow().arity()
     }
 
     pub fn bind(&self, env: &mut EnvironmentTree, instance: Rc<Literal>) -> Self {
@@ -0,0 +1,385 @@
+use crate::environment::Environment;
+use crate::environment::EnvironmentTree;
+use crate::expr_interpret::Result;
+use crate::expr_interpret::RuntimeError;
+use crate::function::LoxFunction;
+use crate::literal::Literal;
+use crate::native_function::NativeMethod;
+use crate::statement::Stmt;
+use crate::stmt_interpret;
+use crate::stmt_interpret::ExecError;
+use crate::stmt_interpret::Iteration;
+use crate::token::Token;
+use indextree::NodeId;
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::io::Write;
+use std::mem;
+use std::rc::Rc;
+
+// Note:
+// A generator can't keep its place on the Rust stack between two calls
+// to next(), so statements that may contain a yield are run by the
+// functions below instead, which unwind into a list of frames when
+// the generator yields and pick up from those frames when it resumes.
+// Everything else (including expressions) still runs as usual, which is
+// why yield is a statement that is only allowed outside of try statements.
+
+// where a suspended generator is within each enclosing statement,
+// the last frame is the outermost one
+enum Frame {
+    // index of the statement within a block or the body
+    Block(usize),
+    // whether it is in the then branch
+    If(bool),
+    While,
+    ForIn(Iteration),
+    // index of the arm, and whether the arm has a binding
+    Match(usize, bool),
+}
+
+enum Flow {
+    Normal,
+    Yield(Rc<Literal>, Vec<Frame>),
+}
+
+enum State {
+    Created(Vec<Rc<Literal>>),
+    // the env of the yield statement and where it is
+    Suspended(NodeId, Vec<Frame>),
+    Running,
+    Done,
+}
+
+pub struct LoxGenerator {
+    function: LoxFunction,
+    state: RefCell<State>,
+    // a value produced by hasNext() that next() has yet to return
+    peeked: RefCell<Option<Rc<Literal>>>,
+}
+
+// every call to a generator function creates a different generator
+impl PartialEq for LoxGenerator {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Display for LoxGenerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<generator {}>",
+            self.function.declaration().borrow().name.lexeme
+        )
+    }
+}
+
+impl LoxGenerator {
+    pub fn new(function: LoxFunction, args: Vec<Rc<Literal>>) -> Self {
+        LoxGenerator {
+            function,
+            state: RefCell::new(State::Created(args)),
+            peeked: RefCell::new(None),
+        }
+    }
+
+    // None once the generator is done
+    // token is the one asking for the value, which is used for reporting errors
+    pub fn next_value<T: Write>(
+        &self,
+        token: &Token,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Option<Rc<Literal>>> {
+        if let Some(value) = self.peeked.borrow_mut().take() {
+            return Ok(Some(value));
+        }
+        self.resume(token, env, output)
+    }
+
+    pub fn has_next<T: Write>(
+        &self,
+        token: &Token,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<bool> {
+        if self.peeked.borrow().is_some() {
+            return Ok(true);
+        }
+        let value = self.resume(token, env, output)?;
+        let has_next = value.is_some();
+        *self.peeked.borrow_mut() = value;
+        Ok(has_next)
+    }
+
+    fn resume<T: Write>(
+        &self,
+        token: &Token,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Option<Rc<Literal>>> {
+        let state = mem::replace(&mut *self.state.borrow_mut(), State::Running);
+        let (prev, frames) = match state {
+            State::Created(args) => {
+                let prev = env.checkout(self.function.closure());
+                env.push(Environment::new());
+                if let Err(error) = self.function.define_params(args, env, output) {
+                    self.finish(prev, env);
+                    return Err(error.within_function(self.function.module(env)));
+                }
+                (prev, None)
+            }
+            State::Suspended(nid, frames) => (env.checkout(nid), Some(frames)),
+            State::Running => {
+                return Err(RuntimeError::new(token, "Generator is already running."))
+            }
+            State::Done => {
+                *self.state.borrow_mut() = State::Done;
+                return Ok(None);
+            }
+        };
+
+        let declaration = self.function.declaration().clone();
+        let result = run_stmts(&declaration.borrow().body, frames, env, output);
+        match result {
+            Ok(Flow::Yield(value, frames)) => {
+                let nid = env.checkout(prev);
+                *self.state.borrow_mut() = State::Suspended(nid, frames);
+                Ok(Some(value))
+            }
+            Ok(Flow::Normal) | Err(ExecError::Return(_)) => {
+                self.finish(prev, env);
+                Ok(None)
+            }
+            Err(ExecError::RuntimeError(error)) | Err(ExecError::Throw(error)) => {
+                self.finish(prev, env);
+                Err(error.within_function(self.function.module(env)))
+            }
+            // resolver rejects break and continue outside of loops
+            Err(ExecError::Break) | Err(ExecError::Continue) => unreachable!(),
+        }
+    }
+
+    // pops the env of the function, like a function call returning
+    fn finish(&self, prev: NodeId, env: &mut EnvironmentTree) {
+        env.pop();
+        env.checkout(prev);
+        *self.state.borrow_mut() = State::Done;
+    }
+}
+
+pub fn get_method(generator: &Rc<Literal>, name: &Token) -> Result<Rc<Literal>> {
+    let method = match name.lexeme.as_str() {
+        "next" => NativeMethod::new("next", 0, generator.clone(), next),
+        "hasNext" => NativeMethod::new("hasNext", 0, generator.clone(), has_next),
+        _ => {
+            return Err(RuntimeError::new(
+                name,
+                &format!("Undefined property '{}'.", name.lexeme),
+            ))
+        }
+    };
+    Ok(Rc::new(Literal::NativeMethodLiteral(method)))
+}
+
+// returns nil once the generator is done
+fn next(
+    receiver: &Rc<Literal>,
+    _args: Vec<Rc<Literal>>,
+    paren: &Token,
+    env: &mut EnvironmentTree,
+    mut output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    let Literal::GeneratorLiteral(ref generator) = **receiver else {
+        unreachable!("Receiver must be a generator")
+    };
+    let value = generator.next_value(paren, env, &mut output)?;
+    Ok(value.unwrap_or_else(|| Rc::new(Literal::Empty)))
+}
+
+fn has_next(
+    receiver: &Rc<Literal>,
+    _args: Vec<Rc<Literal>>,
+    paren: &Token,
+    env: &mut EnvironmentTree,
+    mut output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    let Literal::GeneratorLiteral(ref generator) = **receiver else {
+        unreachable!("Receiver must be a generator")
+    };
+    let has_next = generator.has_next(paren, env, &mut output)?;
+    Ok(Rc::new(Literal::BoolLiteral(has_next)))
+}
+
+type FlowResult = stmt_interpret::Result<Flow>;
+
+// frames is None when the statements run from the start
+fn run_stmts<T: Write>(
+    stmts: &[Stmt],
+    mut frames: Option<Vec<Frame>>,
+    env: &mut EnvironmentTree,
+    output: &mut T,
+) -> FlowResult {
+    let start = match pop_frame(&mut frames) {
+        Some(Frame::Block(i)) => i,
+        None => 0,
+        _ => unreachable!("Invalid generator frame"),
+    };
+    for (i, s) in stmts.iter().enumerate().skip(start) {
+        if let Flow::Yield(value, mut inner) = run_stmt(s, frames.take(), env, output)? {
+            inner.push(Frame::Block(i));
+            return Ok(Flow::Yield(value, inner));
+        }
+    }
+    Ok(Flow::Normal)
+}
+
+fn run_stmt<T: Write>(
+    stmt: &Stmt,
+    mut frames: Option<Vec<Frame>>,
+    env: &mut EnvironmentTree,
+    output: &mut T,
+) -> FlowResult {
+    match stmt {
+        // the env of the block is already there when resuming
+        Stmt::Block(s) => {
+            if frames.is_none() {
+                env.push(Environment::new());
+            }
+            let flow = run_stmts(&s.statements, frames, env, output);
+            leave_scope(&flow, env);
+            flow
+        }
+        Stmt::If(s) => {
+            let then = match pop_frame(&mut frames) {
+                Some(Frame::If(then)) => then,
+                None => s.condition.eval(env, output)?.is_truthy(),
+                _ => unreachable!("Invalid generator frame"),
+            };
+            let branch = if then {
+                Some(&s.then_branch)
+            } else {
+                s.else_branch.as_ref()
+            };
+            match branch {
+                Some(branch) => Ok(suspend(
+                    run_stmt(branch, frames, env, output)?,
+                    Frame::If(then),
+                )),
+                None => Ok(Flow::Normal),
+            }
+        }
+        // the condition is skipped when resuming the body
+        Stmt::While(s) => {
+            if let Some(frame) = pop_frame(&mut frames) {
+                assert!(matches!(frame, Frame::While), "Invalid generator frame");
+            }
+            loop {
+                if frames.is_none() && !s.condition.eval(env, output)?.is_truthy() {
+                    break;
+                }
+                match run_stmt(&s.body, frames.take(), env, output) {
+                    Ok(Flow::Normal) | Err(ExecError::Continue) => {}
+                    Ok(Flow::Yield(value, mut inner)) => {
+                        inner.push(Frame::While);
+                        return Ok(Flow::Yield(value, inner));
+                    }
+                    Err(ExecError::Break) => break,
+                    Err(error) => return Err(error),
+                }
+                if let Some(ref increment) = s.increment {
+                    increment.eval(env, output)?;
+                }
+            }
+            Ok(Flow::Normal)
+        }
+        // the env of the iteration is already there when resuming
+        Stmt::ForIn(s) => {
+            let mut iteration = match pop_frame(&mut frames) {
+                Some(Frame::ForIn(iteration)) => iteration,
+                None => s.iteration(env, output)?,
+                _ => unreachable!("Invalid generator frame"),
+            };
+            loop {
+                if frames.is_none() {
+                    let Some(item) = iteration.next(&s.keyword, env, output)? else {
+                        break;
+                    };
+                    env.push(Environment::new());
+                    env.define(s.name.lexeme.clone(), item);
+                }
+                let flow = run_stmt(&s.body, frames.take(), env, output);
+                leave_scope(&flow, env);
+                match flow {
+                    Ok(Flow::Normal) | Err(ExecError::Continue) => {}
+                    Ok(Flow::Yield(value, mut inner)) => {
+                        inner.push(Frame::ForIn(iteration));
+                        return Ok(Flow::Yield(value, inner));
+                    }
+                    Err(ExecError::Break) => break,
+                    Err(error) => return Err(error),
+                }
+            }
+            Ok(Flow::Normal)
+        }
+        Stmt::Match(s) => {
+            let (index, bound) = match pop_frame(&mut frames) {
+                Some(Frame::Match(index, bound)) => (index, bound),
+                None => {
+                    let Some((index, value)) = s.matching_arm(env, output)? else {
+                        return Ok(Flow::Normal);
+                    };
+                    let binding = s.arms[index].binding();
+                    if let Some(name) = binding {
+                        env.push(Environment::new());
+                        env.define(name.lexeme.clone(), value);
+                    }
+                    (index, binding.is_some())
+                }
+                _ => unreachable!("Invalid generator frame"),
+            };
+            let flow = run_stmt(&s.arms[index].body, frames, env, output);
+            if bound {
+                leave_scope(&flow, env);
+            }
+            Ok(suspend(flow?, Frame::Match(index, bound)))
+        }
+        // resuming right after the yield
+        Stmt::Yield(s) => match frames {
+            Some(_) => Ok(Flow::Normal),
+            None => {
+                let value = match s.value {
+                    Some(ref v) => v.eval(env, output)?,
+                    None => Rc::new(Literal::Empty),
+                };
+                Ok(Flow::Yield(value, vec![]))
+            }
+        },
+        _ => {
+            stmt.execute(env, output)?;
+            Ok(Flow::Normal)
+        }
+    }
+}
+
+fn pop_frame(frames: &mut Option<Vec<Frame>>) -> Option<Frame> {
+    frames.as_mut().and_then(|f| f.pop())
+}
+
+fn suspend(flow: Flow, frame: Frame) -> Flow {
+    match flow {
+        Flow::Yield(value, mut inner) => {
+            inner.push(frame);
+            Flow::Yield(value, inner)
+        }
+        Flow::Normal => Flow::Normal,
+    }
+}
+
+// the env of a suspended statement has to stay for when it resumes
+fn leave_scope(flow: &FlowResult, env: &mut EnvironmentTree) {
+    if !matches!(flow, Ok(Flow::Yield(..))) {
+        env.pop();
+    }
+}
@@ -38,6 +38,10 @@ impl LoxInstance {
         }
     }
 
+    pub fn class(&self) -> &Rc<LoxClass> {
+        &self.class
+    }
+
     pub fn get(
         &mut self,
         name: &Token,
@@ -46,14 +50,8 @@ impl LoxInstance {
     ) -> Result<Rc<Literal>> {
         if let Some(f) = self.fields.get(&name.lexeme) {
             Ok(f.clone())
-        } else if let Some(bm) = self.bound_methods.get(&name.lexeme) {
-            // method is already bound so reuse it
-            Ok(bm.clone())
-        } else if let Some(m) = self.class.find_method(&name.lexeme) {
-            let bound_method = Rc::new(Literal::FunctionLiteral(m.bind(env, instance.clone())));
-            self.bound_methods
-                .insert(name.lexeme.clone(), bound_method.clone());
-            Ok(bound_method)
+        } else if let Some(bm) = self.get_method(&name.lexeme, env, instance) {
+            Ok(bm)
         } else {
             Err(RuntimeError::new(
                 name,
@@ -62,6 +60,24 @@ impl LoxInstance {
         }
     }
 
+    // same as get, but fields are ignored
+    pub fn get_method(
+        &mut self,
+        name: &str,
+        env: &mut EnvironmentTree,
+        instance: &Rc<Literal>,
+    ) -> Option<Rc<Literal>> {
+        if let Some(bm) = self.bound_methods.get(name) {
+            // method is already bound so reuse it
+            return Some(bm.clone());
+        }
+        let m = self.class.find_method(name)?;
+        let bound_method = Rc::new(Literal::FunctionLiteral(m.bind(env, instance.clone())));
+        self.bound_methods
+            .insert(name.to_string(), bound_method.clone());
+        Some(bound_method)
+    }
+
     pub fn set(&mut self, name: String, value: Rc<Literal>) {
         self.fields.insert(name, value);
     }
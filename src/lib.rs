@@ -1,10 +1,15 @@
 mod environment;
 
 mod class;
+mod enumeration;
 mod function;
+mod generator;
 mod instance;
 mod literal;
+mod map;
+mod module;
 mod native_function;
+mod primitive;
 mod token;
 
 mod parser;
@@ -27,11 +32,19 @@ use std::{error::Error, io::Write};
 use environment::EnvironmentTree;
 use literal::Literal;
 use native_function::clock;
+use native_function::has;
+use native_function::keys;
+use native_function::len;
 use native_function::lox;
+use native_function::pop;
+use native_function::push;
+use native_function::remove;
+use native_function::values;
 use native_function::NativeFunction;
 use parser::Parser;
 use resolver::Resolver;
 use scanner::Scanner;
+use statement::Stmt;
 use stmt_interpret::ExecError;
 
 pub use native_function::LOX_ASCII;
@@ -56,16 +69,27 @@ impl Interpreter {
     }
 
     fn init(&mut self) {
-        let clock_fun = NativeFunction::new("native-fn-clock", 0, clock);
-        self.env.define(
-            "clock".to_string(),
-            Rc::new(Literal::NativeFunctionLiteral(clock_fun)),
-        );
-
-        let lox_fun = NativeFunction::new("native-fn-lox", 0, lox);
-        self.env.define(
-            "lox".to_string(),
-            Rc::new(Literal::NativeFunctionLiteral(lox_fun)),
+        let natives = [
+            ("clock", NativeFunction::new("native-fn-clock", 0, clock)),
+            ("lox", NativeFunction::new("native-fn-lox", 0, lox)),
+            ("len", NativeFunction::new("native-fn-len", 1, len)),
+            ("push", NativeFunction::new("native-fn-push", 2, push)),
+            ("pop", NativeFunction::new("native-fn-pop", 1, pop)),
+            ("keys", NativeFunction::new("native-fn-keys", 1, keys)),
+            ("values", NativeFunction::new("native-fn-values", 1, values)),
+            ("has", NativeFunction::new("native-fn-has", 2, has)),
+            ("remove", NativeFunction::new("native-fn-remove", 2, remove)),
+        ];
+
+        for (name, fun) in natives {
+            self.env.define_builtin(
+                name.to_string(),
+                Rc::new(Literal::NativeFunctionLiteral(fun)),
+            );
+        }
+        self.env.define_builtin(
+            "RuntimeError".to_string(),
+            Rc::new(Literal::ClassLiteral(self.env.error_class.clone())),
         );
     }
 
@@ -76,23 +100,16 @@ impl Interpreter {
         error_output: &mut U,
         debug: bool,
     ) -> Result<(), Box<dyn Error>> {
-        let scanner = Scanner::new(source);
-        let tokens = scanner.scan()?;
-
-        let parser = Parser::new(tokens);
-        let mut statements = parser.parse(error_output)?;
-
-        let resolver = Resolver::new();
-        resolver.resolve(&mut statements)?;
-
+        let statements = parse(source, error_output)?;
         for s in statements {
             if debug {
                 write!(output, "AST-START\n{s}\nAST-END\n")?;
             }
             match s.execute(&mut self.env, output) {
                 Ok(_) => {}
-                Err(ExecError::Return(_)) => {}
-                Err(ExecError::RuntimeError(error)) => {
+                // resolver rejects top-level return, break and continue
+                Err(ExecError::Return(_)) | Err(ExecError::Break) | Err(ExecError::Continue) => {}
+                Err(ExecError::RuntimeError(error)) | Err(ExecError::Throw(error)) => {
                     return Err(Box::new(error));
                 }
             }
@@ -100,6 +117,12 @@ impl Interpreter {
         Ok(())
     }
 
+    // imports are resolved relative to the file, or the working directory
+    // if no file is set (e.g. in the REPL)
+    pub fn set_file_path(&mut self, file_path: &str) {
+        self.env.modules.set_main_file(file_path);
+    }
+
     // TODO: how can the users of interpreter distinguish between different errors
     // put different types of errors into an enum?
     pub fn run<T: Write, U: Write>(
@@ -118,3 +141,16 @@ impl Interpreter {
         }
     }
 }
+
+// shared by the main program and imported modules
+fn parse<U: Write>(source: String, error_output: &mut U) -> Result<Vec<Stmt>, Box<dyn Error>> {
+    let scanner = Scanner::new(source);
+    let tokens = scanner.scan()?;
+
+    let parser = Parser::new(tokens);
+    let mut statements = parser.parse(error_output)?;
+
+    let resolver = Resolver::new();
+    resolver.resolve(&mut statements)?;
+    Ok(statements)
+}
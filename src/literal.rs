@@ -1,7 +1,15 @@
 use crate::class::LoxClass;
+use crate::class::LoxTrait;
+use crate::enumeration::EnumValue;
+use crate::enumeration::LoxEnum;
+use crate::enumeration::LoxVariant;
 use crate::function::LoxFunction;
+use crate::generator::LoxGenerator;
 use crate::instance::LoxInstance;
+use crate::map::LoxMap;
+use crate::module::LoxModule;
 use crate::native_function::NativeFunction;
+use crate::native_function::NativeMethod;
 use std::cell::RefCell;
 use std::fmt;
 use std::rc::Rc;
@@ -9,9 +17,11 @@ use std::rc::Rc;
 // Note:
 // LoxClass is in Rc because it can be owned by mutiple instances
 // LoxInstance is in RefCell because it needs to support
-// mutation of its fields through Rc<Literal>
+// mutation of its fields through Rc<Literal>, and so are the elements
+// of a list and the entries of a map
 //
 // This type should probably be 'LoxType' instead since it is not just literals
+#[allow(clippy::enum_variant_names)]
 #[derive(PartialEq)]
 pub enum Literal {
     // primitive
@@ -26,7 +36,16 @@ pub enum Literal {
     FunctionLiteral(LoxFunction),
     NativeFunctionLiteral(NativeFunction),
     ClassLiteral(Rc<LoxClass>),
+    TraitLiteral(Rc<LoxTrait>),
     InstanceLiteral(RefCell<LoxInstance>),
+    ListLiteral(RefCell<Vec<Rc<Literal>>>),
+    MapLiteral(RefCell<LoxMap>),
+    ModuleLiteral(Rc<LoxModule>),
+    GeneratorLiteral(Rc<LoxGenerator>),
+    NativeMethodLiteral(NativeMethod),
+    EnumLiteral(Rc<LoxEnum>),
+    VariantLiteral(Rc<LoxVariant>),
+    EnumValueLiteral(EnumValue),
 }
 
 impl fmt::Display for Literal {
@@ -39,11 +58,67 @@ impl fmt::Display for Literal {
             Literal::FunctionLiteral(ref fun) => write!(f, "{}", fun),
             Literal::NativeFunctionLiteral(ref fun) => write!(f, "{}", fun),
             Literal::ClassLiteral(ref c) => write!(f, "{}", c),
+            Literal::TraitLiteral(ref t) => write!(f, "{}", t),
             Literal::InstanceLiteral(ref i) => write!(f, "{}", i.borrow()),
+            Literal::ListLiteral(ref l) => {
+                let Some(_guard) = NestedGuard::enter(self) else {
+                    return write!(f, "[...]");
+                };
+                let elements: Vec<String> = l.borrow().iter().map(|e| e.to_string()).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Literal::MapLiteral(ref m) => {
+                let Some(_guard) = NestedGuard::enter(self) else {
+                    return write!(f, "{{...}}");
+                };
+                write!(f, "{}", m.borrow())
+            }
+            Literal::ModuleLiteral(ref m) => write!(f, "{}", m),
+            Literal::GeneratorLiteral(ref g) => write!(f, "{}", g),
+            Literal::NativeMethodLiteral(ref m) => write!(f, "{}", m),
+            Literal::EnumLiteral(ref e) => write!(f, "{}", e),
+            Literal::VariantLiteral(ref v) => write!(f, "{}", v),
+            Literal::EnumValueLiteral(ref v) => write!(f, "{}", v),
         }
     }
 }
 
+impl fmt::Debug for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+thread_local! {
+    // lists and maps that are being shown, by address
+    static NESTED: RefCell<Vec<*const Literal>> = const { RefCell::new(Vec::new()) };
+}
+
+// marks a list or map as being shown until it is dropped, so that one
+// that contains itself is shown as [...] or {...} instead of recursing
+// until the stack overflows
+pub struct NestedGuard(*const Literal);
+
+impl NestedGuard {
+    // None if the value is being shown already
+    pub fn enter(value: &Literal) -> Option<Self> {
+        let ptr = value as *const Literal;
+        NESTED.with_borrow_mut(|nested| {
+            if nested.contains(&ptr) {
+                return None;
+            }
+            nested.push(ptr);
+            Some(NestedGuard(ptr))
+        })
+    }
+}
+
+impl Drop for NestedGuard {
+    fn drop(&mut self) {
+        NESTED.with_borrow_mut(|nested| nested.retain(|ptr| *ptr != self.0));
+    }
+}
+
 pub struct OperatorError;
 type Result<T> = std::result::Result<T, OperatorError>;
 
@@ -107,12 +182,40 @@ impl Literal {
         }
     }
 
-    pub fn equal(&self, other: &Literal) -> Literal {
-        Literal::BoolLiteral(self == other)
+    // the result has the same sign as the divisor, so that
+    // a == (a // b) * b + a % b always holds
+    pub fn modulo(&self, other: &Literal) -> Result<Literal> {
+        match *self {
+            Literal::NumberLiteral(a) => match *other {
+                Literal::NumberLiteral(b) => Ok(Literal::NumberLiteral(a - b * (a / b).floor())),
+                _ => Err(OperatorError),
+            },
+            _ => Err(OperatorError),
+        }
+    }
+
+    pub fn floor_divide(&self, other: &Literal) -> Result<Literal> {
+        match *self {
+            Literal::NumberLiteral(a) => match *other {
+                Literal::NumberLiteral(b) => Ok(Literal::NumberLiteral((a / b).floor())),
+                _ => Err(OperatorError),
+            },
+            _ => Err(OperatorError),
+        }
     }
 
-    pub fn not_equal(&self, other: &Literal) -> Literal {
-        Literal::BoolLiteral(self != other)
+    pub fn power(&self, other: &Literal) -> Result<Literal> {
+        match *self {
+            Literal::NumberLiteral(a) => match *other {
+                Literal::NumberLiteral(b) => Ok(Literal::NumberLiteral(a.powf(b))),
+                _ => Err(OperatorError),
+            },
+            _ => Err(OperatorError),
+        }
+    }
+
+    pub fn equal(&self, other: &Literal) -> Literal {
+        Literal::BoolLiteral(self == other)
     }
 
     // TODO: ugh, code duplication
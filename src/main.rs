@@ -43,6 +43,7 @@ pub fn run_file(file_path: &str) {
         process::exit(65);
     });
     let mut interpreter = Interpreter::new();
+    interpreter.set_file_path(file_path);
     interpreter
         .run(contents, &mut io::stdout(), &mut io::stderr(), false)
         .unwrap_or_else(|_| {
@@ -0,0 +1,105 @@
+use crate::literal::Literal;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+// Note:
+// f64 is neither Eq nor Hash, so numbers are stored by their bits.
+// -0.0 is normalized to 0.0 and every NaN to the same NaN, which means
+// that unlike '==', a NaN key can be looked up again.
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub enum MapKey {
+    Bool(bool),
+    Number(u64),
+    String(String),
+}
+
+impl MapKey {
+    pub fn from_literal(l: &Literal) -> Option<MapKey> {
+        match *l {
+            Literal::BoolLiteral(b) => Some(MapKey::Bool(b)),
+            Literal::StringLiteral(ref s) => Some(MapKey::String(s.clone())),
+            Literal::NumberLiteral(n) => {
+                let n = if n.is_nan() {
+                    f64::NAN
+                } else if n == 0.0 {
+                    0.0
+                } else {
+                    n
+                };
+                Some(MapKey::Number(n.to_bits()))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn to_literal(&self) -> Literal {
+        match *self {
+            MapKey::Bool(b) => Literal::BoolLiteral(b),
+            MapKey::Number(bits) => Literal::NumberLiteral(f64::from_bits(bits)),
+            MapKey::String(ref s) => Literal::StringLiteral(s.clone()),
+        }
+    }
+}
+
+// a hash map that remembers the insertion order of its keys
+#[derive(Default)]
+pub struct LoxMap {
+    entries: HashMap<MapKey, Rc<Literal>>,
+    keys: Vec<MapKey>,
+}
+
+impl PartialEq for LoxMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl Display for LoxMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entries: Vec<String> = self
+            .keys
+            .iter()
+            .map(|k| format!("{}: {}", k.to_literal(), self.entries[k]))
+            .collect();
+        write!(f, "{{{}}}", entries.join(", "))
+    }
+}
+
+impl LoxMap {
+    pub fn new() -> Self {
+        LoxMap::default()
+    }
+
+    pub fn get(&self, key: &MapKey) -> Option<&Rc<Literal>> {
+        self.entries.get(key)
+    }
+
+    pub fn insert(&mut self, key: MapKey, value: Rc<Literal>) {
+        if self.entries.insert(key.clone(), value).is_none() {
+            self.keys.push(key);
+        }
+    }
+
+    pub fn remove(&mut self, key: &MapKey) -> Option<Rc<Literal>> {
+        let value = self.entries.remove(key)?;
+        self.keys.retain(|k| k != key);
+        Some(value)
+    }
+
+    pub fn contains_key(&self, key: &MapKey) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &MapKey> {
+        self.keys.iter()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Rc<Literal>> {
+        self.keys.iter().map(|k| &self.entries[k])
+    }
+}
@@ -0,0 +1,159 @@
+use crate::environment::EnvironmentTree;
+use crate::expr_interpret::Result;
+use crate::expr_interpret::RuntimeError;
+use crate::literal::Literal;
+use crate::stmt_interpret::ExecError;
+use crate::token::Token;
+use indextree::NodeId;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+// exports are the globals of a module, which live on in its env
+// after it has been run
+pub struct LoxModule {
+    name: String,
+    nid: NodeId,
+}
+
+// modules are cached, so each file is only ever one module
+impl PartialEq for LoxModule {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Display for LoxModule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<module {}>", self.name)
+    }
+}
+
+impl LoxModule {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn nid(&self) -> NodeId {
+        self.nid
+    }
+
+    pub fn get<'a>(&self, name: &str, env: &'a EnvironmentTree) -> Option<&'a Rc<Literal>> {
+        env.get_export(self.nid, name)
+    }
+}
+
+struct ModuleFile {
+    // the path as written (joined with the importing file), used in messages
+    path: PathBuf,
+    // the canonical path, used to identify the file
+    key: PathBuf,
+}
+
+impl ModuleFile {
+    fn new(path: PathBuf) -> Self {
+        // fall back to the path as is, reading it would fail anyway
+        let key = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        ModuleFile { path, key }
+    }
+}
+
+#[derive(Default)]
+pub struct ModuleLoader {
+    // files being run, the innermost one is at the top
+    files: Vec<ModuleFile>,
+    cache: HashMap<PathBuf, Rc<LoxModule>>,
+}
+
+impl ModuleLoader {
+    // imports in the main file are relative to it, and they are
+    // relative to the working directory if there is no main file
+    pub fn set_main_file(&mut self, path: &str) {
+        self.files = vec![ModuleFile::new(PathBuf::from(path))];
+    }
+
+    fn resolve(&self, relative: &str) -> ModuleFile {
+        match self.files.last().and_then(|f| f.path.parent()) {
+            Some(dir) => ModuleFile::new(dir.join(relative)),
+            None => ModuleFile::new(PathBuf::from(relative)),
+        }
+    }
+}
+
+// runs the module the first time it is imported, and afterwards
+// hands out the cached one
+pub fn import<T: Write>(
+    path: &Token,
+    env: &mut EnvironmentTree,
+    output: &mut T,
+) -> Result<Rc<LoxModule>> {
+    let Literal::StringLiteral(ref relative) = path.literal else {
+        unreachable!("Module path must be a string")
+    };
+    let file = env.modules.resolve(relative);
+    let name = file.path.display().to_string();
+
+    if let Some(module) = env.modules.cache.get(&file.key) {
+        return Ok(module.clone());
+    }
+    if env.modules.files.iter().any(|f| f.key == file.key) {
+        return Err(RuntimeError::new(
+            path,
+            &format!("Circular import of module '{}'.", name),
+        ));
+    }
+
+    let source = fs::read_to_string(&file.path).map_err(|error| {
+        RuntimeError::new(
+            path,
+            &format!("Could not read module '{}': {}", name, error),
+        )
+    })?;
+
+    // parsing errors are only reported, so they are collected here
+    // to fail the import instead
+    let mut error_output = Vec::new();
+    let statements = crate::parse(source, &mut error_output)
+        .map_err(|error| module_error(path, &name, &error.to_string()))?;
+    if !error_output.is_empty() {
+        let error = String::from_utf8_lossy(&error_output);
+        return Err(module_error(path, &name, error.trim_end()));
+    }
+
+    let key = file.key.clone();
+    env.modules.files.push(file);
+    let prev = env.enter_module(name.clone());
+    let result = statements
+        .iter()
+        .try_for_each(|s| match s.execute(env, output) {
+            // resolver rejects top-level return, break and continue
+            Ok(_)
+            | Err(ExecError::Return(_))
+            | Err(ExecError::Break)
+            | Err(ExecError::Continue) => Ok(()),
+            Err(ExecError::RuntimeError(error)) | Err(ExecError::Throw(error)) => Err(error),
+        });
+    let nid = env.leave_module(prev);
+    env.modules.files.pop();
+
+    // imports only happen at the top level, so there is nothing
+    // left to catch a thrown value
+    result.map_err(|mut error| {
+        // the module is named by the outer message already
+        if error.module.as_deref() == Some(name.as_str()) {
+            error.module = None;
+        }
+        module_error(path, &name, &error.located_msg())
+    })?;
+
+    let module = Rc::new(LoxModule { name, nid });
+    env.modules.cache.insert(key, module.clone());
+    Ok(module)
+}
+
+fn module_error(path: &Token, name: &str, msg: &str) -> RuntimeError {
+    RuntimeError::new(path, &format!("Error in module '{}': {}", name, msg))
+}
@@ -1,15 +1,31 @@
+use crate::environment::EnvironmentTree;
+use crate::expr_interpret::map_key;
 use crate::expr_interpret::Result;
 use crate::expr_interpret::RuntimeError;
+use crate::function::Arity;
 use crate::literal::Literal;
+use crate::token::Token;
+use std::cell::RefCell;
 use std::fmt::Display;
+use std::io::Write;
 use std::rc::Rc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(PartialEq, Eq)]
+// takes the arguments and the closing paren of the call
+type NativeFunctionFn = fn(Vec<Rc<Literal>>, &Token) -> Result<Rc<Literal>>;
+
 pub struct NativeFunction {
     name: &'static str,
     arity: usize,
-    fun: fn(Vec<Rc<Literal>>) -> Result<Rc<Literal>>,
+    fun: NativeFunctionFn,
+}
+
+// names of native functions are unique, and comparing function
+// pointers is not reliable anyway
+impl PartialEq for NativeFunction {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
 }
 
 impl Display for NativeFunction {
@@ -19,35 +35,184 @@ impl Display for NativeFunction {
 }
 
 impl NativeFunction {
+    pub fn new(name: &'static str, arity: usize, fun: NativeFunctionFn) -> Self {
+        NativeFunction { name, arity, fun }
+    }
+
+    // errors are reported at the call unless they know their line
+    pub fn call(&self, args: Vec<Rc<Literal>>, paren: &Token) -> Result<Rc<Literal>> {
+        (self.fun)(args, paren).map_err(|mut error| {
+            error.msg = format!("[@{}] {}", self.name, error.msg);
+            error.line = error.line.or(Some(paren.line));
+            error
+        })
+    }
+
+    pub fn arity(&self) -> Arity {
+        Arity::exact(self.arity)
+    }
+}
+
+// takes the receiver, the arguments and the closing paren of the call
+type NativeMethodFn = fn(
+    &Rc<Literal>,
+    Vec<Rc<Literal>>,
+    &Token,
+    &mut EnvironmentTree,
+    &mut dyn Write,
+) -> Result<Rc<Literal>>;
+
+// a method of a built-in type, bound to its receiver
+pub struct NativeMethod {
+    name: &'static str,
+    arity: usize,
+    receiver: Rc<Literal>,
+    fun: NativeMethodFn,
+}
+
+impl PartialEq for NativeMethod {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && Rc::ptr_eq(&self.receiver, &other.receiver)
+    }
+}
+
+impl Display for NativeMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "native-method-{}", self.name)
+    }
+}
+
+impl NativeMethod {
     pub fn new(
         name: &'static str,
         arity: usize,
-        fun: fn(Vec<Rc<Literal>>) -> Result<Rc<Literal>>,
+        receiver: Rc<Literal>,
+        fun: NativeMethodFn,
     ) -> Self {
-        NativeFunction { name, arity, fun }
+        NativeMethod {
+            name,
+            arity,
+            receiver,
+            fun,
+        }
     }
 
-    pub fn call(&self, args: Vec<Rc<Literal>>) -> Result<Rc<Literal>> {
-        (self.fun)(args).map_err(|error| RuntimeError {
-            msg: format!("[@{}] {}", self.name, error.msg),
-        })
+    pub fn call(
+        &self,
+        args: Vec<Rc<Literal>>,
+        paren: &Token,
+        env: &mut EnvironmentTree,
+        output: &mut dyn Write,
+    ) -> Result<Rc<Literal>> {
+        (self.fun)(&self.receiver, args, paren, env, output)
     }
 
-    pub fn arity(&self) -> usize {
-        self.arity
+    pub fn arity(&self) -> Arity {
+        Arity::exact(self.arity)
     }
 }
 
 // example native function
-pub fn clock(_args: Vec<Rc<Literal>>) -> Result<Rc<Literal>> {
+pub fn clock(_args: Vec<Rc<Literal>>, _paren: &Token) -> Result<Rc<Literal>> {
     let start = SystemTime::now();
     if let Ok(since_the_epoch) = start.duration_since(UNIX_EPOCH) {
         let secs = since_the_epoch.as_secs_f64();
         Ok(Rc::new(Literal::NumberLiteral(secs)))
     } else {
-        Err(RuntimeError {
-            msg: "Time went backwards!".to_string(),
-        })
+        Err(RuntimeError::without_line(
+            "Time went backwards!".to_string(),
+        ))
+    }
+}
+
+pub fn len(args: Vec<Rc<Literal>>, _paren: &Token) -> Result<Rc<Literal>> {
+    let len = match *args[0] {
+        Literal::ListLiteral(ref l) => l.borrow().len(),
+        Literal::MapLiteral(ref m) => m.borrow().len(),
+        Literal::StringLiteral(ref s) => s.chars().count(),
+        ref other => {
+            return Err(RuntimeError::without_line(format!(
+                "Can only get the length of lists, maps and strings, got {}.",
+                other
+            )))
+        }
+    };
+    Ok(Rc::new(Literal::NumberLiteral(len as f64)))
+}
+
+pub fn push(args: Vec<Rc<Literal>>, _paren: &Token) -> Result<Rc<Literal>> {
+    if let Literal::ListLiteral(ref l) = *args[0] {
+        l.borrow_mut().push(args[1].clone());
+        Ok(Rc::new(Literal::Empty))
+    } else {
+        Err(RuntimeError::without_line(format!(
+            "Can only push to a list, got {}.",
+            args[0]
+        )))
+    }
+}
+
+pub fn pop(args: Vec<Rc<Literal>>, _paren: &Token) -> Result<Rc<Literal>> {
+    if let Literal::ListLiteral(ref l) = *args[0] {
+        l.borrow_mut()
+            .pop()
+            .ok_or_else(|| RuntimeError::without_line("Can't pop from an empty list.".to_string()))
+    } else {
+        Err(RuntimeError::without_line(format!(
+            "Can only pop from a list, got {}.",
+            args[0]
+        )))
+    }
+}
+
+pub fn keys(args: Vec<Rc<Literal>>, _paren: &Token) -> Result<Rc<Literal>> {
+    if let Literal::MapLiteral(ref m) = *args[0] {
+        let keys = m.borrow().keys().map(|k| Rc::new(k.to_literal())).collect();
+        Ok(Rc::new(Literal::ListLiteral(RefCell::new(keys))))
+    } else {
+        Err(RuntimeError::without_line(format!(
+            "Can only get the keys of a map, got {}.",
+            args[0]
+        )))
+    }
+}
+
+pub fn values(args: Vec<Rc<Literal>>, _paren: &Token) -> Result<Rc<Literal>> {
+    if let Literal::MapLiteral(ref m) = *args[0] {
+        let values = m.borrow().values().cloned().collect();
+        Ok(Rc::new(Literal::ListLiteral(RefCell::new(values))))
+    } else {
+        Err(RuntimeError::without_line(format!(
+            "Can only get the values of a map, got {}.",
+            args[0]
+        )))
+    }
+}
+
+pub fn has(args: Vec<Rc<Literal>>, paren: &Token) -> Result<Rc<Literal>> {
+    if let Literal::MapLiteral(ref m) = *args[0] {
+        let key = map_key(paren, &args[1])?;
+        Ok(Rc::new(Literal::BoolLiteral(m.borrow().contains_key(&key))))
+    } else {
+        Err(RuntimeError::without_line(format!(
+            "Can only look up keys in a map, got {}.",
+            args[0]
+        )))
+    }
+}
+
+// returns the removed value, or nil if the key is missing
+pub fn remove(args: Vec<Rc<Literal>>, paren: &Token) -> Result<Rc<Literal>> {
+    if let Literal::MapLiteral(ref m) = *args[0] {
+        let key = map_key(paren, &args[1])?;
+        Ok(m.borrow_mut()
+            .remove(&key)
+            .unwrap_or_else(|| Rc::new(Literal::Empty)))
+    } else {
+        Err(RuntimeError::without_line(format!(
+            "Can only remove keys from a map, got {}.",
+            args[0]
+        )))
     }
 }
 
@@ -69,6 +234,6 @@ pub static LOX_ASCII: &str = r"
                        `---'     `--`  
 ";
 
-pub fn lox(_args: Vec<Rc<Literal>>) -> Result<Rc<Literal>> {
+pub fn lox(_args: Vec<Rc<Literal>>, _paren: &Token) -> Result<Rc<Literal>> {
     Ok(Rc::new(Literal::StringLiteral(LOX_ASCII.to_string())))
 }
@@ -64,6 +64,17 @@ impl Parser {
         self.peek().token_type == tt
     }
 
+    fn check_next(&self, tt: TokenType) -> bool {
+        self.check_nth(1, tt)
+    }
+
+    fn check_nth(&self, n: usize, tt: TokenType) -> bool {
+        match self.tokens.get(self.current + n) {
+            Some(t) => t.token_type == tt,
+            None => false,
+        }
+    }
+
     fn is_at_end(&self) -> bool {
         self.check(TokenType::EOF)
     }
@@ -126,10 +137,23 @@ impl Parser {
     fn declaration(&mut self) -> Result<Stmt> {
         if self.match_one(TokenType::CLASS).is_some() {
             self.class_declaration()
-        } else if self.match_one(TokenType::FUN).is_some() {
+        } else if self.match_one(TokenType::TRAIT).is_some() {
+            self.trait_declaration()
+        } else if self.match_one(TokenType::ENUM).is_some() {
+            self.enum_declaration()
+        } else if self.check(TokenType::FUN)
+            && (self.check_next(TokenType::IDENTIFIER)
+                || self.check_next(TokenType::STAR) && self.check_nth(2, TokenType::IDENTIFIER))
+        {
+            // otherwise it is an expression statement of an anonymous function
+            self.advance();
             self.fun_declaration("function")
         } else if self.match_one(TokenType::VAR).is_some() {
             self.var_declaration()
+        } else if self.match_one(TokenType::CONST).is_some() {
+            self.const_declaration()
+        } else if let Some(token) = self.match_one(TokenType::IMPORT) {
+            self.import_declaration(token)
         } else {
             self.statement()
         }
@@ -148,44 +172,144 @@ impl Parser {
             None
         };
 
+        let mut traits = vec![];
+        if self.match_contextual_keyword("with").is_some() {
+            loop {
+                let trait_name = self.expect_one(TokenType::IDENTIFIER, "Expect trait name.")?;
+                traits.push(VarExpr {
+                    name: trait_name,
+                    scope_offset: None,
+                });
+                if self.match_one(TokenType::COMMA).is_none() {
+                    break;
+                }
+            }
+        }
+
         self.expect_one(TokenType::LEFT_BRACE, "Expect '{' before class body.")?;
         let mut methods = vec![];
+        let mut static_methods = vec![];
         while !self.check(TokenType::RIGHT_BRACE) && !self.is_at_end() {
-            methods.push(Rc::new(RefCell::new(self.function("method")?)));
+            if self.match_one(TokenType::CLASS).is_some() {
+                static_methods.push(Rc::new(RefCell::new(self.function("static method")?)));
+            } else {
+                methods.push(Rc::new(RefCell::new(self.function("method")?)));
+            }
         }
         self.expect_one(TokenType::RIGHT_BRACE, "Expect '}' after class body.")?;
 
         Ok(Stmt::Class(ClassStmt {
             name,
             methods,
+            static_methods,
             superclass,
+            traits,
         }))
     }
 
+    fn trait_declaration(&mut self) -> Result<Stmt> {
+        let name = self.expect_one(TokenType::IDENTIFIER, "Expect trait name.")?;
+
+        self.expect_one(TokenType::LEFT_BRACE, "Expect '{' before trait body.")?;
+        let mut methods = vec![];
+        while !self.check(TokenType::RIGHT_BRACE) && !self.is_at_end() {
+            methods.push(Rc::new(RefCell::new(self.function("method")?)));
+        }
+        self.expect_one(TokenType::RIGHT_BRACE, "Expect '}' after trait body.")?;
+
+        Ok(Stmt::Trait(TraitStmt { name, methods }))
+    }
+
+    fn enum_declaration(&mut self) -> Result<Stmt> {
+        let name = self.expect_one(TokenType::IDENTIFIER, "Expect enum name.")?;
+
+        self.expect_one(TokenType::LEFT_BRACE, "Expect '{' before enum body.")?;
+        let mut variants = vec![];
+        while !self.check(TokenType::RIGHT_BRACE) && !self.is_at_end() {
+            let variant_name = self.expect_one(TokenType::IDENTIFIER, "Expect variant name.")?;
+            let mut fields = vec![];
+            if self.match_one(TokenType::LEFT_PAREN).is_some() {
+                loop {
+                    fields.push(self.expect_one(TokenType::IDENTIFIER, "Expect field name.")?);
+                    if self.match_one(TokenType::COMMA).is_none() {
+                        break;
+                    }
+                }
+                self.expect_one(TokenType::RIGHT_PAREN, "Expect ')' after fields.")?;
+            }
+            variants.push(EnumVariant {
+                name: variant_name,
+                fields,
+            });
+            if self.match_one(TokenType::COMMA).is_none() {
+                break;
+            }
+        }
+        self.expect_one(TokenType::RIGHT_BRACE, "Expect '}' after enum body.")?;
+
+        Ok(Stmt::Enum(EnumStmt { name, variants }))
+    }
+
     fn function(&mut self, kind: &str) -> Result<FunctionStmt> {
         let name = self.expect_one(TokenType::IDENTIFIER, &format!("Expect {} name.", kind))?;
-        self.expect_one(
-            TokenType::LEFT_PAREN,
-            &format!("Expect '(' afeter {} name.", kind),
-        )?;
+        self.function_body(name, kind)
+    }
 
-        let mut params = vec![];
-        if !self.check(TokenType::RIGHT_PAREN) {
-            loop {
-                if params.len() >= 255 {
-                    return Err(ParsingError::new(
-                        self.peek(),
-                        "Can't have more than 255 parameters.",
-                    ));
-                }
-                params.push(self.expect_one(TokenType::IDENTIFIER, "Expect parameter name")?);
-                if self.match_one(TokenType::COMMA).is_none() {
-                    break;
+    // parses parameters and body of a function whose name has been consumed
+    fn function_body(&mut self, name: Token, kind: &str) -> Result<FunctionStmt> {
+        // methods declared without a parameter list are getters
+        let is_getter = kind == "method" && self.check(TokenType::LEFT_BRACE);
+
+        let mut params: Vec<Param> = vec![];
+        let mut rest = None;
+        if !is_getter {
+            self.expect_one(
+                TokenType::LEFT_PAREN,
+                &format!("Expect '(' afeter {} name.", kind),
+            )?;
+
+            if !self.check(TokenType::RIGHT_PAREN) {
+                loop {
+                    if params.len() >= 255 {
+                        return Err(ParsingError::new(
+                            self.peek(),
+                            "Can't have more than 255 parameters.",
+                        ));
+                    }
+                    if self.match_one(TokenType::DOT_DOT_DOT).is_some() {
+                        rest = Some(
+                            self.expect_one(TokenType::IDENTIFIER, "Expect rest parameter name.")?,
+                        );
+                        if self.check(TokenType::COMMA) {
+                            return Err(ParsingError::new(
+                                self.peek(),
+                                "Rest parameter must be the last parameter.",
+                            ));
+                        }
+                        break;
+                    }
+
+                    let name = self.expect_one(TokenType::IDENTIFIER, "Expect parameter name")?;
+                    let default = if self.match_one(TokenType::EQUAL).is_some() {
+                        Some(self.expression()?)
+                    } else if params.last().is_some_and(|p| p.default.is_some()) {
+                        return Err(ParsingError::new(
+                            &name,
+                            "Parameter without a default can't follow one with a default.",
+                        ));
+                    } else {
+                        None
+                    };
+                    params.push(Param { name, default });
+                    if self.match_one(TokenType::COMMA).is_none() {
+                        break;
+                    }
                 }
             }
+
+            self.expect_one(TokenType::RIGHT_PAREN, "Expect ')' after parameters.")?;
         }
 
-        self.expect_one(TokenType::RIGHT_PAREN, "Expect ')' after parameters.")?;
         self.expect_one(
             TokenType::LEFT_BRACE,
             &format!("Expect '{{' before {} body.", kind),
@@ -196,15 +320,29 @@ impl Parser {
             body.push(self.declaration()?);
         }
         self.expect_one(TokenType::RIGHT_BRACE, "Expect '}' after body.")?;
-        Ok(FunctionStmt { name, params, body })
+        Ok(FunctionStmt {
+            name,
+            params,
+            rest,
+            body,
+            is_getter,
+            is_generator: false,
+        })
     }
 
     fn fun_declaration(&mut self, kind: &str) -> Result<Stmt> {
-        let fun = self.function(kind)?;
+        let is_generator = self.match_one(TokenType::STAR).is_some();
+        let mut fun = self.function(kind)?;
+        fun.is_generator = is_generator;
         Ok(Stmt::Function(Rc::new(RefCell::new(fun))))
     }
 
     fn var_declaration(&mut self) -> Result<Stmt> {
+        if let Some(bracket) =
+            self.match_one_of(vec![TokenType::LEFT_BRACKET, TokenType::LEFT_BRACE])
+        {
+            return self.destructuring_declaration(bracket, false);
+        }
         let token = self.expect_one(TokenType::IDENTIFIER, "Expect variable name")?;
         let mut initializer = None;
         if self.match_one(TokenType::EQUAL).is_some() {
@@ -219,20 +357,148 @@ impl Parser {
         Ok(Stmt::Var(VarStmt {
             name: token,
             value: initializer,
+            is_const: false,
+        }))
+    }
+
+    fn const_declaration(&mut self) -> Result<Stmt> {
+        if let Some(bracket) =
+            self.match_one_of(vec![TokenType::LEFT_BRACKET, TokenType::LEFT_BRACE])
+        {
+            return self.destructuring_declaration(bracket, true);
+        }
+        let token = self.expect_one(TokenType::IDENTIFIER, "Expect constant name.")?;
+        self.expect_one(TokenType::EQUAL, "Expect '=' after constant name.")?;
+        let initializer = self.expression()?;
+        self.expect_one(
+            TokenType::SEMICOLON,
+            "Expect ';' after constant declaration.",
+        )?;
+
+        Ok(Stmt::Var(VarStmt {
+            name: token,
+            value: Some(initializer),
+            is_const: true,
         }))
     }
 
+    fn destructuring_declaration(&mut self, bracket: Token, is_const: bool) -> Result<Stmt> {
+        let (closing, closing_msg) = if bracket.token_type == TokenType::LEFT_BRACKET {
+            (
+                TokenType::RIGHT_BRACKET,
+                "Expect ']' after destructured names.",
+            )
+        } else {
+            (
+                TokenType::RIGHT_BRACE,
+                "Expect '}' after destructured names.",
+            )
+        };
+        let mut names = vec![];
+        loop {
+            names.push(self.expect_one(TokenType::IDENTIFIER, "Expect variable name")?);
+            if self.match_one(TokenType::COMMA).is_none() {
+                break;
+            }
+        }
+        self.expect_one(closing, closing_msg)?;
+        self.expect_one(TokenType::EQUAL, "Expect '=' after destructuring pattern.")?;
+        let value = self.expression()?;
+        self.expect_one(
+            TokenType::SEMICOLON,
+            "Expect ';' after variable declaration.",
+        )?;
+
+        Ok(Stmt::Destructure(DestructureStmt {
+            pattern: Destructuring { bracket, names },
+            value,
+            is_const,
+        }))
+    }
+
+    // 'as' and 'from' are only keywords in an import, so they can still
+    // be used as identifiers elsewhere
+    fn import_declaration(&mut self, keyword: Token) -> Result<Stmt> {
+        let mut alias = None;
+        let mut names = vec![];
+        let path = if self.match_one(TokenType::LEFT_BRACE).is_some() {
+            loop {
+                names.push(self.expect_one(TokenType::IDENTIFIER, "Expect imported name.")?);
+                if self.match_one(TokenType::COMMA).is_none() {
+                    break;
+                }
+            }
+            self.expect_one(TokenType::RIGHT_BRACE, "Expect '}' after imported names.")?;
+            self.expect_contextual_keyword("from", "Expect 'from' after imported names.")?;
+            self.expect_one(TokenType::STRING, "Expect module path.")?
+        } else {
+            let path = self.expect_one(TokenType::STRING, "Expect module path.")?;
+            self.expect_contextual_keyword("as", "Expect 'as' after module path.")?;
+            alias = Some(self.expect_one(TokenType::IDENTIFIER, "Expect module name.")?);
+            path
+        };
+        self.expect_one(TokenType::SEMICOLON, "Expect ';' after import.")?;
+
+        Ok(Stmt::Import(ImportStmt {
+            keyword,
+            path,
+            alias,
+            names,
+        }))
+    }
+
+    fn expect_contextual_keyword(&mut self, keyword: &str, error_msg: &str) -> Result<Token> {
+        self.match_contextual_keyword(keyword)
+            .ok_or_else(|| ParsingError::new(self.peek(), error_msg))
+    }
+
+    fn match_contextual_keyword(&mut self, keyword: &str) -> Option<Token> {
+        if self.check(TokenType::IDENTIFIER) && self.peek().lexeme == keyword {
+            Some(self.advance())
+        } else {
+            None
+        }
+    }
+
     fn statement(&mut self) -> Result<Stmt> {
         if self.match_one(TokenType::IF).is_some() {
             self.if_statement()
         } else if self.match_one(TokenType::WHILE).is_some() {
             self.while_statetment()
-        } else if self.match_one(TokenType::FOR).is_some() {
-            self.for_statetment()
+        } else if let Some(token) = self.match_one(TokenType::FOR) {
+            self.for_statetment(token)
         } else if self.match_one(TokenType::PRINT).is_some() {
             self.print_statement()
         } else if let Some(token) = self.match_one(TokenType::RETURN) {
             self.return_statement(token)
+        } else if let Some(token) = self.match_one(TokenType::YIELD) {
+            let value = if self.check(TokenType::SEMICOLON) {
+                None
+            } else {
+                Some(self.expression()?)
+            };
+            self.expect_one(TokenType::SEMICOLON, "Expect ';' after yield value.")?;
+            Ok(Stmt::Yield(YieldStmt {
+                keyword: token,
+                value,
+            }))
+        } else if let Some(token) = self.match_one(TokenType::THROW) {
+            let value = self.expression()?;
+            self.expect_one(TokenType::SEMICOLON, "Expect ';' after thrown value.")?;
+            Ok(Stmt::Throw(ThrowStmt {
+                keyword: token,
+                value,
+            }))
+        } else if let Some(token) = self.match_one(TokenType::TRY) {
+            self.try_statement(token)
+        } else if self.match_one(TokenType::MATCH).is_some() {
+            self.match_statement()
+        } else if let Some(token) = self.match_one(TokenType::BREAK) {
+            self.expect_one(TokenType::SEMICOLON, "Expect ';' after 'break'.")?;
+            Ok(Stmt::Break(BreakStmt { keyword: token }))
+        } else if let Some(token) = self.match_one(TokenType::CONTINUE) {
+            self.expect_one(TokenType::SEMICOLON, "Expect ';' after 'continue'.")?;
+            Ok(Stmt::Continue(ContinueStmt { keyword: token }))
         } else if self.match_one(TokenType::LEFT_BRACE).is_some() {
             self.block_statement()
         } else {
@@ -250,9 +516,19 @@ impl Parser {
         Ok(Stmt::Return(ReturnStmt { keyword, value }))
     }
 
-    fn for_statetment(&mut self) -> Result<Stmt> {
+    fn for_statetment(&mut self, keyword: Token) -> Result<Stmt> {
         self.expect_one(TokenType::LEFT_PAREN, "Expect '(' after 'for'.")?;
 
+        // 'in' is only a keyword right after the loop variable
+        let is_for_in = self.check(TokenType::IDENTIFIER)
+            && self
+                .tokens
+                .get(self.current + 1)
+                .is_some_and(|t| t.token_type == TokenType::IDENTIFIER && t.lexeme == "in");
+        if is_for_in {
+            return self.for_in_statement(keyword);
+        }
+
         let initializer = if self.match_one(TokenType::SEMICOLON).is_some() {
             None
         } else if self.match_one(TokenType::VAR).is_some() {
@@ -278,19 +554,13 @@ impl Parser {
         self.expect_one(TokenType::RIGHT_PAREN, "Expect ')' after for clauses.")?;
 
         // desugaring for loop into block statement + while loop
-        let mut body = self.statement()?;
-
-        // including increment right after while body
-        if let Some(i) = increment {
-            body = Stmt::Block(BlockStmt {
-                statements: vec![body, Stmt::Expr(ExprStmt { expr: i })],
-            })
-        }
+        let body = self.statement()?;
 
-        // constructing while
+        // constructing while, which runs increment right after its body
         let mut while_stmt = Stmt::While(WhileStmt {
             condition,
             body: Box::new(body),
+            increment,
         });
 
         // including initializer right before while statement
@@ -303,12 +573,31 @@ impl Parser {
         Ok(while_stmt)
     }
 
+    fn for_in_statement(&mut self, keyword: Token) -> Result<Stmt> {
+        let name = self.advance();
+        self.advance();
+        let iterable = self.expression()?;
+        self.expect_one(TokenType::RIGHT_PAREN, "Expect ')' after for clauses.")?;
+        let body = Box::new(self.statement()?);
+
+        Ok(Stmt::ForIn(ForInStmt {
+            keyword,
+            name,
+            iterable,
+            body,
+        }))
+    }
+
     fn while_statetment(&mut self) -> Result<Stmt> {
         self.expect_one(TokenType::LEFT_PAREN, "Expect '(' after 'while'.")?;
         let condition = self.expression()?;
         self.expect_one(TokenType::RIGHT_PAREN, "Expect ')' after while condition.")?;
         let body = Box::new(self.statement()?);
-        Ok(Stmt::While(WhileStmt { condition, body }))
+        Ok(Stmt::While(WhileStmt {
+            condition,
+            body,
+            increment: None,
+        }))
     }
 
     fn if_statement(&mut self) -> Result<Stmt> {
@@ -329,13 +618,109 @@ impl Parser {
         }))
     }
 
+    fn try_statement(&mut self, keyword: Token) -> Result<Stmt> {
+        self.expect_one(TokenType::LEFT_BRACE, "Expect '{' after 'try'.")?;
+        let try_block = self.block()?;
+
+        let mut catch_name = None;
+        let mut catch_block = None;
+        if self.match_one(TokenType::CATCH).is_some() {
+            self.expect_one(TokenType::LEFT_PAREN, "Expect '(' after 'catch'.")?;
+            catch_name = Some(self.expect_one(TokenType::IDENTIFIER, "Expect exception name.")?);
+            self.expect_one(TokenType::RIGHT_PAREN, "Expect ')' after exception name.")?;
+            self.expect_one(TokenType::LEFT_BRACE, "Expect '{' before catch body.")?;
+            catch_block = Some(self.block()?);
+        }
+
+        let mut finally_block = None;
+        if self.match_one(TokenType::FINALLY).is_some() {
+            self.expect_one(TokenType::LEFT_BRACE, "Expect '{' after 'finally'.")?;
+            finally_block = Some(self.block()?);
+        }
+
+        if catch_block.is_none() && finally_block.is_none() {
+            return Err(ParsingError::new(
+                &keyword,
+                "Expect 'catch' or 'finally' after try block.",
+            ));
+        }
+
+        Ok(Stmt::Try(TryStmt {
+            try_block,
+            catch_name,
+            catch_block,
+            finally_block,
+        }))
+    }
+
+    fn match_statement(&mut self) -> Result<Stmt> {
+        self.expect_one(TokenType::LEFT_PAREN, "Expect '(' after 'match'.")?;
+        let value = self.expression()?;
+        self.expect_one(TokenType::RIGHT_PAREN, "Expect ')' after match value.")?;
+        self.expect_one(TokenType::LEFT_BRACE, "Expect '{' before match arms.")?;
+
+        let mut arms = vec![];
+        while !self.check(TokenType::RIGHT_BRACE) && !self.is_at_end() {
+            let mut patterns = vec![self.pattern()?];
+            while self.match_one(TokenType::COMMA).is_some() {
+                patterns.push(self.pattern()?);
+            }
+            self.expect_one(TokenType::EQUAL_GREATER, "Expect '=>' after match pattern.")?;
+            let body = Box::new(self.statement()?);
+            arms.push(MatchArm { patterns, body });
+        }
+        self.expect_one(TokenType::RIGHT_BRACE, "Expect '}' after match arms.")?;
+
+        Ok(Stmt::Match(MatchStmt { value, arms }))
+    }
+
+    fn pattern(&mut self) -> Result<Pattern> {
+        if self.check(TokenType::IDENTIFIER) {
+            // _ is a wildcard when it's a pattern of its own, including
+            // as one of several alternatives
+            let ends_pattern =
+                self.check_next(TokenType::EQUAL_GREATER) || self.check_next(TokenType::COMMA);
+            if self.peek().lexeme == "_" && ends_pattern {
+                self.advance();
+                return Ok(Pattern::Wildcard);
+            }
+            // a name or a dotted one followed by the binding
+            let mut n = 1;
+            while self.check_nth(n, TokenType::DOT) && self.check_nth(n + 1, TokenType::IDENTIFIER)
+            {
+                n += 2;
+            }
+            if self.check_nth(n, TokenType::IDENTIFIER) {
+                let mut class = Box::new(Expr::Var(VarExpr {
+                    name: self.advance(),
+                    scope_offset: None,
+                }));
+                while self.match_one(TokenType::DOT).is_some() {
+                    class = Box::new(Expr::Get(GetExpr {
+                        object: class,
+                        name: self.advance(),
+                        optional: false,
+                    }));
+                }
+                let name = self.advance();
+                return Ok(Pattern::Class { class, name });
+            }
+        }
+        Ok(Pattern::Value(self.or()?))
+    }
+
     fn block_statement(&mut self) -> Result<Stmt> {
+        Ok(Stmt::Block(self.block()?))
+    }
+
+    // the opening brace has been consumed
+    fn block(&mut self) -> Result<BlockStmt> {
         let mut statements = vec![];
         while !self.check(TokenType::RIGHT_BRACE) && !self.is_at_end() {
             statements.push(self.declaration()?);
         }
         self.expect_one(TokenType::RIGHT_BRACE, "Expect '}' after block.")?;
-        Ok(Stmt::Block(BlockStmt { statements }))
+        Ok(BlockStmt { statements })
     }
 
     fn print_statement(&mut self) -> Result<Stmt> {
@@ -355,29 +740,147 @@ impl Parser {
     }
 
     fn assignment(&mut self) -> Result<Box<Expr>> {
-        let expr = self.or()?;
+        let expr = self.conditional()?;
         if let Some(token) = self.match_one(TokenType::EQUAL) {
-            match *expr {
-                Expr::Var(e) => {
-                    let value = self.assignment()?;
-                    return Ok(Box::new(Expr::Assign(AssignExpr {
-                        name: e.name,
-                        value,
-                        scope_offset: None,
-                    })));
-                }
-                Expr::Get(e) => {
-                    let value = self.assignment()?;
-                    return Ok(Box::new(Expr::Set(SetExpr {
-                        object: e.object,
-                        name: e.name,
-                        value,
-                    })));
-                }
-                _ => {
-                    return Err(ParsingError::new(&token, "Invalid assignment target."));
-                }
+            let value = self.assignment()?;
+            return self.assignment_target(expr, token, value, false);
+        }
+
+        if let Some(token) = self.match_one_of(vec![
+            TokenType::PLUS_EQUAL,
+            TokenType::MINUS_EQUAL,
+            TokenType::STAR_EQUAL,
+            TokenType::SLASH_EQUAL,
+        ]) {
+            let value = self.assignment()?;
+            return self.assignment_target(expr, token, value, false);
+        }
+
+        Ok(expr)
+    }
+
+    // turns the target of an assignment into its assignment expression,
+    // which is a compound assignment unless the operator is '='
+    // whether any link of a chain of accesses and calls is optional
+    fn is_optional_chain(expr: &Expr) -> bool {
+        match expr {
+            Expr::Get(e) => e.optional || Self::is_optional_chain(&e.object),
+            Expr::Index(e) => Self::is_optional_chain(&e.object),
+            Expr::Call(e) => Self::is_optional_chain(&e.callee),
+            _ => false,
+        }
+    }
+
+    fn assignment_target(
+        &self,
+        target: Box<Expr>,
+        operator: Token,
+        value: Box<Expr>,
+        postfix: bool,
+    ) -> Result<Box<Expr>> {
+        let is_valid = match *target {
+            Expr::Var(_) => true,
+            // a?.b = c is not allowed since there might be nothing to assign to,
+            // and neither is a?.b.c = d, as the whole chain short-circuits
+            Expr::Get(ref e) => !e.optional && !Self::is_optional_chain(&e.object),
+            Expr::Index(ref e) => !Self::is_optional_chain(&e.object),
+            // [a, b] = c destructures c into variables
+            Expr::List(ref e) => {
+                operator.token_type == TokenType::EQUAL
+                    && !e.elements.is_empty()
+                    && e.elements.iter().all(|e| matches!(e, Expr::Var(_)))
             }
+            _ => false,
+        };
+        if !is_valid {
+            return Err(ParsingError::new(&operator, "Invalid assignment target."));
+        }
+
+        let compound = if operator.token_type == TokenType::EQUAL {
+            None
+        } else {
+            Some(CompoundOp { operator, postfix })
+        };
+
+        match *target {
+            Expr::Var(e) => Ok(Box::new(Expr::Assign(AssignExpr {
+                name: e.name,
+                value,
+                scope_offset: None,
+                compound,
+            }))),
+            Expr::Get(e) => Ok(Box::new(Expr::Set(SetExpr {
+                object: e.object,
+                name: e.name,
+                value,
+                compound,
+            }))),
+            Expr::Index(e) => Ok(Box::new(Expr::IndexSet(IndexSetExpr {
+                object: e.object,
+                bracket: e.bracket,
+                index: e.index,
+                value,
+                compound,
+            }))),
+            Expr::List(e) => {
+                let names = e
+                    .elements
+                    .into_iter()
+                    .map(|e| match e {
+                        Expr::Var(v) => v.name,
+                        _ => unreachable!(),
+                    })
+                    .collect();
+                Ok(Box::new(Expr::Destructure(DestructureExpr {
+                    pattern: Destructuring {
+                        bracket: e.bracket,
+                        names,
+                    },
+                    value,
+                    scope_offsets: vec![],
+                })))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    // ++a and a++ are a += 1, except that the latter evaluates to the old value
+    fn increment(&self, target: Box<Expr>, token: Token, postfix: bool) -> Result<Box<Expr>> {
+        let value = Box::new(Expr::Literal(LiteralExpr {
+            value: Literal::NumberLiteral(1.0),
+        }));
+        self.assignment_target(target, token, value, postfix)
+    }
+
+    // right-associative, so a ? b : c ? d : e is a ? b : (c ? d : e)
+    fn conditional(&mut self) -> Result<Box<Expr>> {
+        let condition = self.coalesce()?;
+        if self.match_one(TokenType::QUESTION).is_some() {
+            let then_branch = self.expression()?;
+            self.expect_one(
+                TokenType::COLON,
+                "Expect ':' after then branch of conditional expression.",
+            )?;
+            let else_branch = self.conditional()?;
+            return Ok(Box::new(Expr::Conditional(ConditionalExpr {
+                condition,
+                then_branch,
+                else_branch,
+            })));
+        }
+        Ok(condition)
+    }
+
+    // a ?? b only evaluates b if a is nil
+    fn coalesce(&mut self) -> Result<Box<Expr>> {
+        let mut expr = self.or()?;
+        while let Some(token) = self.match_one(TokenType::QUESTION_QUESTION) {
+            let rhs = self.or()?;
+            expr = Box::new(Expr::Logical(LogicalExpr {
+                left: expr,
+                operator: token,
+                right: rhs,
+            }))
         }
 
         Ok(expr)
@@ -463,7 +966,12 @@ impl Parser {
 
     fn factor(&mut self) -> Result<Box<Expr>> {
         let mut expr = self.unary()?;
-        while let Some(token) = self.match_one_of(vec![TokenType::SLASH, TokenType::STAR]) {
+        while let Some(token) = self.match_one_of(vec![
+            TokenType::SLASH,
+            TokenType::STAR,
+            TokenType::PERCENT,
+            TokenType::SLASH_SLASH,
+        ]) {
             let rhs = self.unary()?;
             expr = Box::new(Expr::Binary(BinaryExpr {
                 left: expr,
@@ -475,6 +983,11 @@ impl Parser {
     }
 
     fn unary(&mut self) -> Result<Box<Expr>> {
+        if let Some(token) = self.match_one_of(vec![TokenType::PLUS_PLUS, TokenType::MINUS_MINUS]) {
+            let target = self.unary()?;
+            return self.increment(target, token, false);
+        }
+
         if let Some(token) = self.match_one_of(vec![TokenType::BANG, TokenType::MINUS]) {
             let rhs = self.unary()?;
             Ok(Box::new(Expr::Unary(UnaryExpr {
@@ -482,8 +995,31 @@ impl Parser {
                 right: rhs,
             })))
         } else {
-            self.call()
+            self.power()
+        }
+    }
+
+    // '**' binds tighter than unary operators on its left (-2 ** 2 == -4)
+    // and is right-associative through unary on its right (2 ** -1 == 0.5)
+    fn power(&mut self) -> Result<Box<Expr>> {
+        let expr = self.postfix()?;
+        if let Some(token) = self.match_one(TokenType::STAR_STAR) {
+            let rhs = self.unary()?;
+            return Ok(Box::new(Expr::Binary(BinaryExpr {
+                left: expr,
+                operator: token,
+                right: rhs,
+            })));
         }
+        Ok(expr)
+    }
+
+    fn postfix(&mut self) -> Result<Box<Expr>> {
+        let expr = self.call()?;
+        if let Some(token) = self.match_one_of(vec![TokenType::PLUS_PLUS, TokenType::MINUS_MINUS]) {
+            return self.increment(expr, token, true);
+        }
+        Ok(expr)
     }
 
     fn call(&mut self) -> Result<Box<Expr>> {
@@ -494,7 +1030,28 @@ impl Parser {
             } else if self.match_one(TokenType::DOT).is_some() {
                 let name =
                     self.expect_one(TokenType::IDENTIFIER, "Expect property name after '.'.")?;
-                expr = Box::new(Expr::Get(GetExpr { object: expr, name }))
+                expr = Box::new(Expr::Get(GetExpr {
+                    object: expr,
+                    name,
+                    optional: false,
+                }))
+            } else if self.match_one(TokenType::QUESTION_DOT).is_some() {
+                let name =
+                    self.expect_one(TokenType::IDENTIFIER, "Expect property name after '?.'.")?;
+                expr = Box::new(Expr::Get(GetExpr {
+                    object: expr,
+                    name,
+                    optional: true,
+                }))
+            } else if self.match_one(TokenType::LEFT_BRACKET).is_some() {
+                let index = self.expression()?;
+                let bracket =
+                    self.expect_one(TokenType::RIGHT_BRACKET, "Expect ']' after index.")?;
+                expr = Box::new(Expr::Index(IndexExpr {
+                    object: expr,
+                    bracket,
+                    index,
+                }))
             } else {
                 break;
             }
@@ -550,6 +1107,31 @@ impl Parser {
             })));
         }
 
+        // string interpolation
+        if let Some(token) = self.match_one(TokenType::INTERPOLATION) {
+            let mut parts = vec![Expr::Literal(LiteralExpr {
+                value: token.literal,
+            })];
+            loop {
+                parts.push(*(self.expression()?));
+                if let Some(token) = self.match_one(TokenType::INTERPOLATION) {
+                    parts.push(Expr::Literal(LiteralExpr {
+                        value: token.literal,
+                    }));
+                } else {
+                    let token = self.expect_one(
+                        TokenType::STRING,
+                        "Expect '}' after interpolated expression.",
+                    )?;
+                    parts.push(Expr::Literal(LiteralExpr {
+                        value: token.literal,
+                    }));
+                    break;
+                }
+            }
+            return Ok(Box::new(Expr::Interpolation(InterpolationExpr { parts })));
+        }
+
         // super
         if let Some(token) = self.match_one(TokenType::SUPER) {
             self.expect_one(TokenType::DOT, "Expect '.' after 'super'.")?;
@@ -562,6 +1144,16 @@ impl Parser {
             })));
         }
 
+        // anonymous function
+        if let Some(token) = self.match_one(TokenType::FUN) {
+            let is_generator = self.match_one(TokenType::STAR).is_some();
+            let mut fun = self.function_body(token, "anonymous function")?;
+            fun.is_generator = is_generator;
+            return Ok(Box::new(Expr::Function(FunctionExpr {
+                declaration: Rc::new(RefCell::new(fun)),
+            })));
+        }
+
         // this
         if let Some(token) = self.match_one(TokenType::THIS) {
             return Ok(Box::new(Expr::This(ThisExpr {
@@ -578,6 +1170,40 @@ impl Parser {
             })));
         }
 
+        // list
+        if let Some(bracket) = self.match_one(TokenType::LEFT_BRACKET) {
+            let mut elements = vec![];
+            if !self.check(TokenType::RIGHT_BRACKET) {
+                loop {
+                    elements.push(*(self.expression()?));
+                    if self.match_one(TokenType::COMMA).is_none() {
+                        break;
+                    }
+                }
+            }
+            self.expect_one(TokenType::RIGHT_BRACKET, "Expect ']' after list elements.")?;
+            return Ok(Box::new(Expr::List(ListExpr { bracket, elements })));
+        }
+
+        // map, which never conflicts with a block since
+        // a statement starting with '{' is always parsed as a block
+        if self.match_one(TokenType::LEFT_BRACE).is_some() {
+            let mut entries = vec![];
+            if !self.check(TokenType::RIGHT_BRACE) {
+                loop {
+                    let key = self.expression()?;
+                    self.expect_one(TokenType::COLON, "Expect ':' after map key.")?;
+                    let value = self.expression()?;
+                    entries.push((*key, *value));
+                    if self.match_one(TokenType::COMMA).is_none() {
+                        break;
+                    }
+                }
+            }
+            let brace = self.expect_one(TokenType::RIGHT_BRACE, "Expect '}' after map entries.")?;
+            return Ok(Box::new(Expr::Map(MapExpr { brace, entries })));
+        }
+
         // grouping
         if self.match_one(TokenType::LEFT_PAREN).is_some() {
             let expr = self.expression()?;
@@ -593,13 +1219,21 @@ impl Parser {
         while t.token_type != TokenType::SEMICOLON && !self.is_at_end() {
             match self.peek().token_type {
                 TokenType::CLASS
+                | TokenType::TRAIT
+                | TokenType::ENUM
                 | TokenType::FUN
                 | TokenType::VAR
+                | TokenType::CONST
                 | TokenType::FOR
                 | TokenType::IF
                 | TokenType::WHILE
                 | TokenType::PRINT
-                | TokenType::RETURN => return,
+                | TokenType::RETURN
+                | TokenType::YIELD
+                | TokenType::THROW
+                | TokenType::TRY
+                | TokenType::IMPORT
+                | TokenType::MATCH => return,
                 _ => {}
             }
 
@@ -0,0 +1,205 @@
+use crate::environment::EnvironmentTree;
+use crate::expr_interpret::Result;
+use crate::expr_interpret::RuntimeError;
+use crate::literal::Literal;
+use crate::native_function::NativeMethod;
+use crate::token::Token;
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+// properties of strings and numbers, which are all methods bound to
+// the value except for the length of a string
+pub fn get_method(value: &Rc<Literal>, name: &Token) -> Result<Rc<Literal>> {
+    let receiver = value.clone();
+    let method = match (&**value, name.lexeme.as_str()) {
+        (Literal::StringLiteral(s), "length") => {
+            return Ok(Rc::new(Literal::NumberLiteral(s.chars().count() as f64)))
+        }
+        (Literal::StringLiteral(_), "upper") => NativeMethod::new("upper", 0, receiver, upper),
+        (Literal::StringLiteral(_), "lower") => NativeMethod::new("lower", 0, receiver, lower),
+        (Literal::StringLiteral(_), "trim") => NativeMethod::new("trim", 0, receiver, trim),
+        (Literal::StringLiteral(_), "contains") => {
+            NativeMethod::new("contains", 1, receiver, contains)
+        }
+        (Literal::StringLiteral(_), "startsWith") => {
+            NativeMethod::new("startsWith", 1, receiver, starts_with)
+        }
+        (Literal::StringLiteral(_), "endsWith") => {
+            NativeMethod::new("endsWith", 1, receiver, ends_with)
+        }
+        (Literal::StringLiteral(_), "split") => NativeMethod::new("split", 1, receiver, split),
+        (Literal::NumberLiteral(_), "floor") => NativeMethod::new("floor", 0, receiver, floor),
+        (Literal::NumberLiteral(_), "ceil") => NativeMethod::new("ceil", 0, receiver, ceil),
+        (Literal::NumberLiteral(_), "round") => NativeMethod::new("round", 0, receiver, round),
+        (Literal::NumberLiteral(_), "abs") => NativeMethod::new("abs", 0, receiver, abs),
+        _ => {
+            return Err(RuntimeError::new(
+                name,
+                &format!("Undefined property '{}'.", name.lexeme),
+            ))
+        }
+    };
+    Ok(Rc::new(Literal::NativeMethodLiteral(method)))
+}
+
+fn receiver_string(receiver: &Literal) -> &str {
+    let Literal::StringLiteral(ref s) = *receiver else {
+        unreachable!("Receiver must be a string")
+    };
+    s
+}
+
+fn receiver_number(receiver: &Literal) -> f64 {
+    let Literal::NumberLiteral(n) = *receiver else {
+        unreachable!("Receiver must be a number")
+    };
+    n
+}
+
+fn string_arg<'a>(arg: &'a Literal, paren: &Token) -> Result<&'a str> {
+    match *arg {
+        Literal::StringLiteral(ref s) => Ok(s),
+        _ => Err(RuntimeError::new(
+            paren,
+            &format!("Argument must be a string, got {}.", arg),
+        )),
+    }
+}
+
+fn string(s: String) -> Result<Rc<Literal>> {
+    Ok(Rc::new(Literal::StringLiteral(s)))
+}
+
+fn boolean(b: bool) -> Result<Rc<Literal>> {
+    Ok(Rc::new(Literal::BoolLiteral(b)))
+}
+
+fn number(n: f64) -> Result<Rc<Literal>> {
+    Ok(Rc::new(Literal::NumberLiteral(n)))
+}
+
+fn upper(
+    receiver: &Rc<Literal>,
+    _args: Vec<Rc<Literal>>,
+    _paren: &Token,
+    _env: &mut EnvironmentTree,
+    _output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    string(receiver_string(receiver).to_uppercase())
+}
+
+fn lower(
+    receiver: &Rc<Literal>,
+    _args: Vec<Rc<Literal>>,
+    _paren: &Token,
+    _env: &mut EnvironmentTree,
+    _output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    string(receiver_string(receiver).to_lowercase())
+}
+
+fn trim(
+    receiver: &Rc<Literal>,
+    _args: Vec<Rc<Literal>>,
+    _paren: &Token,
+    _env: &mut EnvironmentTree,
+    _output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    string(receiver_string(receiver).trim().to_string())
+}
+
+fn contains(
+    receiver: &Rc<Literal>,
+    args: Vec<Rc<Literal>>,
+    paren: &Token,
+    _env: &mut EnvironmentTree,
+    _output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    let pattern = string_arg(&args[0], paren)?;
+    boolean(receiver_string(receiver).contains(pattern))
+}
+
+fn starts_with(
+    receiver: &Rc<Literal>,
+    args: Vec<Rc<Literal>>,
+    paren: &Token,
+    _env: &mut EnvironmentTree,
+    _output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    let prefix = string_arg(&args[0], paren)?;
+    boolean(receiver_string(receiver).starts_with(prefix))
+}
+
+fn ends_with(
+    receiver: &Rc<Literal>,
+    args: Vec<Rc<Literal>>,
+    paren: &Token,
+    _env: &mut EnvironmentTree,
+    _output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    let suffix = string_arg(&args[0], paren)?;
+    boolean(receiver_string(receiver).ends_with(suffix))
+}
+
+// an empty separator splits a string into its characters
+fn split(
+    receiver: &Rc<Literal>,
+    args: Vec<Rc<Literal>>,
+    paren: &Token,
+    _env: &mut EnvironmentTree,
+    _output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    let s = receiver_string(receiver);
+    let separator = string_arg(&args[0], paren)?;
+    let parts: Vec<Rc<Literal>> = if separator.is_empty() {
+        s.chars()
+            .map(|c| Rc::new(Literal::StringLiteral(c.to_string())))
+            .collect()
+    } else {
+        s.split(separator)
+            .map(|p| Rc::new(Literal::StringLiteral(p.to_string())))
+            .collect()
+    };
+    Ok(Rc::new(Literal::ListLiteral(RefCell::new(parts))))
+}
+
+fn floor(
+    receiver: &Rc<Literal>,
+    _args: Vec<Rc<Literal>>,
+    _paren: &Token,
+    _env: &mut EnvironmentTree,
+    _output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    number(receiver_number(receiver).floor())
+}
+
+fn ceil(
+    receiver: &Rc<Literal>,
+    _args: Vec<Rc<Literal>>,
+    _paren: &Token,
+    _env: &mut EnvironmentTree,
+    _output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    number(receiver_number(receiver).ceil())
+}
+
+fn round(
+    receiver: &Rc<Literal>,
+    _args: Vec<Rc<Literal>>,
+    _paren: &Token,
+    _env: &mut EnvironmentTree,
+    _output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    number(receiver_number(receiver).round())
+}
+
+fn abs(
+    receiver: &Rc<Literal>,
+    _args: Vec<Rc<Literal>>,
+    _paren: &Token,
+    _env: &mut EnvironmentTree,
+    _output: &mut dyn Write,
+) -> Result<Rc<Literal>> {
+    number(receiver_number(receiver).abs())
+}
@@ -3,7 +3,15 @@ use crate::token::Token;
 use std::collections::HashMap;
 use std::{error::Error, fmt};
 
-type Scope = HashMap<String, bool>;
+// line is where the variable is declared, which errors about
+// constants refer to
+pub struct Variable {
+    pub defined: bool,
+    pub is_const: bool,
+    pub line: usize,
+}
+
+type Scope = HashMap<String, Variable>;
 
 #[derive(PartialEq, Eq)]
 pub enum FunctionType {
@@ -11,6 +19,20 @@ pub enum FunctionType {
     Fun,
     Method,
     Initializer,
+    Generator,
+}
+
+#[derive(PartialEq, Eq)]
+pub enum LoopType {
+    NonLoop,
+    Loop,
+}
+
+// generators can't suspend within a try statement
+#[derive(PartialEq, Eq)]
+pub enum TryType {
+    NonTry,
+    Try,
 }
 
 #[derive(PartialEq, Eq)]
@@ -18,6 +40,10 @@ pub enum ClassType {
     NonClass,
     Class,
     Subclass,
+    // within a static method, where there is no instance
+    Static,
+    // within a trait, where there is an instance but no superclass
+    Trait,
 }
 
 #[derive(Debug)]
@@ -46,6 +72,8 @@ pub struct Resolver {
     scopes: Vec<Scope>,
     pub current_fun: FunctionType,
     pub current_cls: ClassType,
+    pub current_loop: LoopType,
+    pub current_try: TryType,
 }
 
 impl Resolver {
@@ -54,6 +82,8 @@ impl Resolver {
             scopes: vec![],
             current_fun: FunctionType::NonFun,
             current_cls: ClassType::NonClass,
+            current_loop: LoopType::NonLoop,
+            current_try: TryType::NonTry,
         }
     }
 
@@ -73,25 +103,49 @@ impl Resolver {
                     "Already a variable with this name in this scope.",
                 ));
             }
-            s.insert(name.lexeme.clone(), false);
+            let variable = Variable {
+                defined: false,
+                is_const: false,
+                line: name.line,
+            };
+            s.insert(name.lexeme.clone(), variable);
+        }
+        Ok(())
+    }
+
+    pub fn declare_const(&mut self, name: &Token) -> Result<()> {
+        self.declare(name)?;
+        if let Some(v) = self.peek().and_then(|s| s.get_mut(&name.lexeme)) {
+            v.is_const = true;
         }
         Ok(())
     }
 
     pub fn define(&mut self, name: &Token) {
-        if let Some(s) = self.peek() {
-            s.insert(name.lexeme.clone(), true);
+        if let Some(v) = self.peek().and_then(|s| s.get_mut(&name.lexeme)) {
+            v.defined = true;
         }
     }
 
-    pub fn get(&mut self, name: &Token) -> Option<&bool> {
+    // for 'this' and 'super', which are never declared in the source
+    pub fn define_keyword(&mut self, keyword: &str) {
+        let variable = Variable {
+            defined: true,
+            is_const: true,
+            line: 0,
+        };
+        self.peek()
+            .expect("Empty scopes")
+            .insert(keyword.to_string(), variable);
+    }
+
+    pub fn get(&mut self, name: &Token) -> Option<&Variable> {
         let s = self.peek()?;
         s.get(&name.lexeme)
     }
 
     // theoretically we should have a &self equivalent
-    // this method should be used *publicly* only by ClassStmt to define 'this'
-    pub fn peek(&mut self) -> Option<&mut Scope> {
+    fn peek(&mut self) -> Option<&mut Scope> {
         if self.scopes.is_empty() {
             None
         } else {
@@ -100,6 +154,10 @@ impl Resolver {
         }
     }
 
+    pub fn is_global_scope(&self) -> bool {
+        self.scopes.is_empty()
+    }
+
     pub fn resolve_local(&self, name: &Token) -> Option<usize> {
         for (i, s) in self.scopes.iter().rev().enumerate() {
             if s.contains_key(&name.lexeme) {
@@ -109,6 +167,25 @@ impl Resolver {
         None
     }
 
+    // same as resolve_local, but constants can't be the target
+    pub fn resolve_assignment(&self, name: &Token) -> Result<Option<usize>> {
+        for (i, s) in self.scopes.iter().rev().enumerate() {
+            if let Some(v) = s.get(&name.lexeme) {
+                if v.is_const {
+                    return Err(ResolutionError::new(
+                        name,
+                        &format!(
+                            "Can't assign to constant '{}' declared on line {}.",
+                            name.lexeme, v.line
+                        ),
+                    ));
+                }
+                return Ok(Some(i));
+            }
+        }
+        Ok(None)
+    }
+
     pub fn resolve(mut self, statements: &mut [Stmt]) -> Result<()> {
         for s in statements.iter_mut() {
             s.resolve(&mut self)?;
@@ -10,6 +10,8 @@ pub struct Scanner {
     start: usize,
     current: usize,
     line: usize,
+    // unmatched '{' within each enclosing string interpolation
+    interpolations: Vec<usize>,
 }
 
 #[derive(Debug)]
@@ -44,6 +46,7 @@ impl Scanner {
             start: 0,
             current: 0,
             line: 1,
+            interpolations: vec![],
         }
     }
 
@@ -53,6 +56,13 @@ impl Scanner {
             self.scan_token()?;
         }
 
+        if !self.interpolations.is_empty() {
+            return Err(ScanningError::new(
+                self.line,
+                "Unterminated string interpolation",
+            ));
+        }
+
         self.tokens.push(Token::new(
             TokenType::EOF,
             String::from(""),
@@ -72,14 +82,79 @@ impl Scanner {
         match c {
             '(' => self.add_token(TokenType::LEFT_PAREN, Literal::Empty),
             ')' => self.add_token(TokenType::RIGHT_PAREN, Literal::Empty),
-            '{' => self.add_token(TokenType::LEFT_BRACE, Literal::Empty),
-            '}' => self.add_token(TokenType::RIGHT_BRACE, Literal::Empty),
+            '{' => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                self.add_token(TokenType::LEFT_BRACE, Literal::Empty);
+            }
+            '}' => match self.interpolations.last_mut() {
+                // end of an interpolated expression, so resume the string
+                Some(0) => {
+                    self.interpolations.pop();
+                    self.string()?;
+                }
+                Some(depth) => {
+                    *depth -= 1;
+                    self.add_token(TokenType::RIGHT_BRACE, Literal::Empty);
+                }
+                None => self.add_token(TokenType::RIGHT_BRACE, Literal::Empty),
+            },
+            '[' => self.add_token(TokenType::LEFT_BRACKET, Literal::Empty),
+            ']' => self.add_token(TokenType::RIGHT_BRACKET, Literal::Empty),
             ',' => self.add_token(TokenType::COMMA, Literal::Empty),
-            '.' => self.add_token(TokenType::DOT, Literal::Empty),
-            '-' => self.add_token(TokenType::MINUS, Literal::Empty),
-            '+' => self.add_token(TokenType::PLUS, Literal::Empty),
+            ':' => self.add_token(TokenType::COLON, Literal::Empty),
+            '.' => {
+                if self.peek() == '.' && self.peek_next() == '.' {
+                    self.advance();
+                    self.advance();
+                    self.add_token(TokenType::DOT_DOT_DOT, Literal::Empty);
+                } else {
+                    self.add_token(TokenType::DOT, Literal::Empty);
+                }
+            }
+            '-' => {
+                let t = if self.match_next('=') {
+                    TokenType::MINUS_EQUAL
+                } else if self.match_next('-') {
+                    TokenType::MINUS_MINUS
+                } else {
+                    TokenType::MINUS
+                };
+                self.add_token(t, Literal::Empty);
+            }
+            '+' => {
+                let t = if self.match_next('=') {
+                    TokenType::PLUS_EQUAL
+                } else if self.match_next('+') {
+                    TokenType::PLUS_PLUS
+                } else {
+                    TokenType::PLUS
+                };
+                self.add_token(t, Literal::Empty);
+            }
             ';' => self.add_token(TokenType::SEMICOLON, Literal::Empty),
-            '*' => self.add_token(TokenType::STAR, Literal::Empty),
+            '?' => {
+                let t = if self.match_next('.') {
+                    TokenType::QUESTION_DOT
+                } else if self.match_next('?') {
+                    TokenType::QUESTION_QUESTION
+                } else {
+                    TokenType::QUESTION
+                };
+                self.add_token(t, Literal::Empty);
+            }
+            '%' => self.add_token(TokenType::PERCENT, Literal::Empty),
+            '*' => {
+                let t = if self.match_next('*') {
+                    TokenType::STAR_STAR
+                } else if self.match_next('=') {
+                    TokenType::STAR_EQUAL
+                } else {
+                    TokenType::STAR
+                };
+                self.add_token(t, Literal::Empty);
+            }
             '!' => {
                 let t = if self.match_next('=') {
                     TokenType::BANG_EQUAL
@@ -91,6 +166,8 @@ impl Scanner {
             '=' => {
                 let t = if self.match_next('=') {
                     TokenType::EQUAL_EQUAL
+                } else if self.match_next('>') {
+                    TokenType::EQUAL_GREATER
                 } else {
                     TokenType::EQUAL
                 };
@@ -113,10 +190,17 @@ impl Scanner {
                 self.add_token(t, Literal::Empty);
             }
             '/' => {
-                if self.match_next('/') {
+                if self.peek() == '/' && self.follows_operand() {
+                    self.advance();
+                    self.add_token(TokenType::SLASH_SLASH, Literal::Empty);
+                } else if self.match_next('/') {
                     while self.peek() != '\n' && !self.is_at_end() {
                         self.advance();
                     }
+                } else if self.match_next('*') {
+                    self.block_comment()?;
+                } else if self.match_next('=') {
+                    self.add_token(TokenType::SLASH_EQUAL, Literal::Empty);
                 } else {
                     self.add_token(TokenType::SLASH, Literal::Empty);
                 }
@@ -128,6 +212,7 @@ impl Scanner {
                 self.line += 1;
             }
             '"' => self.string()?,
+            'r' if self.peek() == '"' => self.raw_string()?,
             _ => {
                 if Self::is_digit(c) {
                     self.number();
@@ -172,12 +257,87 @@ impl Scanner {
         }
     }
 
+    // '//' is floor division right after an operand on the same line
+    // (e.g. 'a // b'), and starts a comment anywhere else
+    fn follows_operand(&self) -> bool {
+        self.tokens.last().is_some_and(|t| {
+            t.line == self.line
+                && matches!(
+                    t.token_type,
+                    TokenType::NUMBER
+                        | TokenType::STRING
+                        | TokenType::IDENTIFIER
+                        | TokenType::RIGHT_PAREN
+                        | TokenType::RIGHT_BRACKET
+                        | TokenType::NIL
+                        | TokenType::TRUE
+                        | TokenType::FALSE
+                        | TokenType::THIS
+                )
+        })
+    }
+
     fn add_token(&mut self, t: TokenType, l: Literal) {
         let text: String = self.source[self.start..self.current].iter().collect();
         self.tokens.push(Token::new(t, text, l, self.line));
     }
 
+    // block comments can be nested, and an unterminated one
+    // is reported at the line where it starts
+    fn block_comment(&mut self) -> Result<()> {
+        let start_line = self.line;
+        let mut depth = 1;
+        while depth > 0 {
+            if self.is_at_end() {
+                return Err(ScanningError::new(start_line, "Unterminated block comment"));
+            }
+
+            let c = self.advance();
+            if c == '\n' {
+                self.line += 1;
+            } else if c == '/' && self.match_next('*') {
+                depth += 1;
+            } else if c == '*' && self.match_next('/') {
+                depth -= 1;
+            }
+        }
+        Ok(())
+    }
+
     fn string(&mut self) -> Result<()> {
+        let mut value = String::new();
+        while self.peek() != '"' && !self.is_at_end() {
+            let c = self.advance();
+            match c {
+                '\n' => {
+                    self.line += 1;
+                    value.push(c);
+                }
+                '\\' => value.push(self.escape()?),
+                '$' if self.peek() == '{' => {
+                    self.advance();
+                    self.interpolations.push(0);
+                    self.add_token(TokenType::INTERPOLATION, Literal::StringLiteral(value));
+                    return Ok(());
+                }
+                _ => value.push(c),
+            }
+        }
+
+        if self.is_at_end() {
+            return Err(ScanningError::new(self.line, "Unterminated string"));
+        }
+
+        self.advance();
+
+        self.add_token(TokenType::STRING, Literal::StringLiteral(value));
+        Ok(())
+    }
+
+    // raw strings (e.g. r"\d+") are taken verbatim without escape sequences
+    fn raw_string(&mut self) -> Result<()> {
+        // consume the opening quote
+        self.advance();
         while self.peek() != '"' && !self.is_at_end() {
             if self.peek() == '\n' {
                 self.line += 1;
@@ -191,7 +351,7 @@ impl Scanner {
 
         self.advance();
 
-        let value: String = self.source[self.start + 1..self.current - 1]
+        let value: String = self.source[self.start + 2..self.current - 1]
             .iter()
             .collect();
 
@@ -199,12 +359,67 @@ impl Scanner {
         Ok(())
     }
 
+    // the backslash has been consumed
+    fn escape(&mut self) -> Result<char> {
+        if self.is_at_end() {
+            return Err(ScanningError::new(self.line, "Unterminated string"));
+        }
+
+        let c = self.advance();
+        match c {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            'r' => Ok('\r'),
+            '0' => Ok('\0'),
+            '\\' => Ok('\\'),
+            '"' => Ok('"'),
+            '$' => Ok('$'),
+            'u' => self.unicode_escape(),
+            _ => Err(ScanningError::new(
+                self.line,
+                &format!("Invalid escape sequence '\\{}'", c),
+            )),
+        }
+    }
+
+    // \u{...} with 1 to 6 hex digits
+    fn unicode_escape(&mut self) -> Result<char> {
+        if !self.match_next('{') {
+            return Err(ScanningError::new(
+                self.line,
+                "Expect '{' after '\\u' in unicode escape sequence",
+            ));
+        }
+
+        let mut digits = String::new();
+        while self.peek().is_ascii_hexdigit() && digits.len() < 6 {
+            digits.push(self.advance());
+        }
+
+        if digits.is_empty() || !self.match_next('}') {
+            return Err(ScanningError::new(
+                self.line,
+                "Invalid unicode escape sequence, expect 1 to 6 hex digits in '\\u{...}'",
+            ));
+        }
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| {
+                ScanningError::new(
+                    self.line,
+                    &format!("Invalid unicode code point '\\u{{{}}}'", digits),
+                )
+            })
+    }
+
     fn is_digit(c: char) -> bool {
-        ('0'..='9').contains(&c)
+        c.is_ascii_digit()
     }
 
     fn is_alpha(c: char) -> bool {
-        ('a'..='z').contains(&c) || ('A'..='Z').contains(&c) || c == '_'
+        c.is_ascii_alphabetic() || c == '_'
     }
 
     fn is_alpha_numeric(c: char) -> bool {
@@ -251,21 +466,33 @@ impl Scanner {
         // TODO: too lazy to use lazy static and think about lifetime :/
         match s {
             "and" => Some(TokenType::AND),
+            "break" => Some(TokenType::BREAK),
+            "catch" => Some(TokenType::CATCH),
             "class" => Some(TokenType::CLASS),
+            "const" => Some(TokenType::CONST),
+            "continue" => Some(TokenType::CONTINUE),
             "else" => Some(TokenType::ELSE),
+            "enum" => Some(TokenType::ENUM),
             "false" => Some(TokenType::FALSE),
+            "finally" => Some(TokenType::FINALLY),
             "for" => Some(TokenType::FOR),
             "fun" => Some(TokenType::FUN),
             "if" => Some(TokenType::IF),
+            "import" => Some(TokenType::IMPORT),
+            "match" => Some(TokenType::MATCH),
             "nil" => Some(TokenType::NIL),
             "or" => Some(TokenType::OR),
             "print" => Some(TokenType::PRINT),
             "return" => Some(TokenType::RETURN),
             "super" => Some(TokenType::SUPER),
             "this" => Some(TokenType::THIS),
+            "throw" => Some(TokenType::THROW),
+            "trait" => Some(TokenType::TRAIT),
             "true" => Some(TokenType::TRUE),
+            "try" => Some(TokenType::TRY),
             "var" => Some(TokenType::VAR),
             "while" => Some(TokenType::WHILE),
+            "yield" => Some(TokenType::YIELD),
             _ => None,
         }
     }
@@ -1,4 +1,4 @@
-use crate::expr::{Expr, VarExpr};
+use crate::expr::{Destructuring, Expr, VarExpr};
 use crate::token::Token;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -8,16 +8,28 @@ use std::rc::Rc;
 // and LoxFunction. It is in RefCell because Resolver needs to
 // mutate exprs within FunctionStmt to save scope_offset. And the
 // mutation is safe because only Resolver borrows it mutably and exclusively.
+#[allow(clippy::large_enum_variant)]
 pub enum Stmt {
     Expr(ExprStmt),
     Print(PrintStmt),
     Var(VarStmt),
+    Destructure(DestructureStmt),
     Block(BlockStmt),
     If(IfStmt),
     While(WhileStmt),
     Function(Rc<RefCell<FunctionStmt>>),
     Return(ReturnStmt),
     Class(ClassStmt),
+    Trait(TraitStmt),
+    Enum(EnumStmt),
+    Break(BreakStmt),
+    Continue(ContinueStmt),
+    Throw(ThrowStmt),
+    Try(TryStmt),
+    Import(ImportStmt),
+    Match(MatchStmt),
+    ForIn(ForInStmt),
+    Yield(YieldStmt),
 }
 
 pub struct ExprStmt {
@@ -28,9 +40,34 @@ pub struct PrintStmt {
     pub expr: Box<Expr>,
 }
 
+// only allowed directly in a generator, so it never has to suspend
+// an expression halfway
+pub struct YieldStmt {
+    pub keyword: Token,
+    pub value: Option<Box<Expr>>,
+}
+
+// iterates over lists, maps (their keys), strings (their characters),
+// and instances whose iterator() returns an object with hasNext() and next()
+pub struct ForInStmt {
+    pub keyword: Token,
+    pub name: Token,
+    pub iterable: Box<Expr>,
+    pub body: Box<Stmt>,
+}
+
+// constants always have a value
 pub struct VarStmt {
     pub name: Token,
     pub value: Option<Box<Expr>>,
+    pub is_const: bool,
+}
+
+// var [a, b] = value; or var {x, y} = value;
+pub struct DestructureStmt {
+    pub pattern: Destructuring,
+    pub value: Box<Expr>,
+    pub is_const: bool,
 }
 
 pub struct BlockStmt {
@@ -43,15 +80,30 @@ pub struct IfStmt {
     pub else_branch: Option<Box<Stmt>>,
 }
 
+// increment is only set when desugaring a for loop, so that
+// 'continue' still runs it before the next iteration
 pub struct WhileStmt {
     pub condition: Box<Expr>,
     pub body: Box<Stmt>,
+    pub increment: Option<Box<Expr>>,
 }
 
 pub struct FunctionStmt {
     pub name: Token,
-    pub params: Vec<Token>,
+    pub params: Vec<Param>,
+    // collects the arguments left over by params into a list
+    pub rest: Option<Token>,
     pub body: Vec<Stmt>,
+    // a method without a parameter list, which is called on access
+    pub is_getter: bool,
+    // declared with 'fun*', calling it returns a generator
+    pub is_generator: bool,
+}
+
+// params with a default are always after the ones without
+pub struct Param {
+    pub name: Token,
+    pub default: Option<Box<Expr>>,
 }
 
 pub struct ReturnStmt {
@@ -62,5 +114,75 @@ pub struct ReturnStmt {
 pub struct ClassStmt {
     pub name: Token,
     pub methods: Vec<Rc<RefCell<FunctionStmt>>>,
+    pub static_methods: Vec<Rc<RefCell<FunctionStmt>>>,
     pub superclass: Option<VarExpr>,
+    // methods of traits are merged into the class
+    pub traits: Vec<VarExpr>,
+}
+
+pub struct TraitStmt {
+    pub name: Token,
+    pub methods: Vec<Rc<RefCell<FunctionStmt>>>,
+}
+
+pub struct EnumStmt {
+    pub name: Token,
+    pub variants: Vec<EnumVariant>,
+}
+
+// a variant without fields is declared without parentheses
+pub struct EnumVariant {
+    pub name: Token,
+    pub fields: Vec<Token>,
+}
+
+pub struct BreakStmt {
+    pub keyword: Token,
+}
+
+pub struct ContinueStmt {
+    pub keyword: Token,
+}
+
+pub struct ThrowStmt {
+    pub keyword: Token,
+    pub value: Box<Expr>,
+}
+
+// at least one of catch_block and finally_block is present
+pub struct TryStmt {
+    pub try_block: BlockStmt,
+    pub catch_name: Option<Token>,
+    pub catch_block: Option<BlockStmt>,
+    pub finally_block: Option<BlockStmt>,
+}
+
+// either alias or names is set, depending on whether the whole module
+// or only some of its exports are imported
+pub struct ImportStmt {
+    pub keyword: Token,
+    pub path: Token,
+    pub alias: Option<Token>,
+    pub names: Vec<Token>,
+}
+
+// arms are tried in order, and at most one of them is executed
+pub struct MatchStmt {
+    pub value: Box<Expr>,
+    pub arms: Vec<MatchArm>,
+}
+
+pub struct MatchArm {
+    pub patterns: Vec<Pattern>,
+    pub body: Box<Stmt>,
+}
+
+// a value pattern that evaluates to a class is a class pattern
+// without a binding
+#[allow(clippy::large_enum_variant)]
+pub enum Pattern {
+    Value(Box<Expr>),
+    // class is a name or a dotted one (e.g. Shape.Circle)
+    Class { class: Box<Expr>, name: Token },
+    Wildcard,
 }
@@ -8,12 +8,23 @@ impl Display for Stmt {
             Stmt::Expr(s) => write!(f, "{}", s),
             Stmt::Print(s) => write!(f, "{}", s),
             Stmt::Var(s) => write!(f, "{}", s),
+            Stmt::Destructure(s) => write!(f, "{}", s),
             Stmt::Block(s) => write!(f, "{}", s),
             Stmt::If(s) => write!(f, "{}", s),
             Stmt::While(s) => write!(f, "{}", s),
             Stmt::Return(s) => write!(f, "{}", s),
             Stmt::Function(s) => write!(f, "{}", s.borrow()),
             Stmt::Class(s) => write!(f, "{}", s),
+            Stmt::Trait(s) => write!(f, "{}", s),
+            Stmt::Enum(s) => write!(f, "{}", s),
+            Stmt::Break(_) => write!(f, "(break)"),
+            Stmt::Continue(_) => write!(f, "(continue)"),
+            Stmt::Throw(s) => write!(f, "{}", s),
+            Stmt::Try(s) => write!(f, "{}", s),
+            Stmt::Import(s) => write!(f, "{}", s),
+            Stmt::Match(s) => write!(f, "{}", s),
+            Stmt::ForIn(s) => write!(f, "{}", s),
+            Stmt::Yield(s) => write!(f, "{}", s),
         }
     }
 }
@@ -35,12 +46,23 @@ impl Display for ExprStmt {
 impl Display for VarStmt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.value {
+            Some(ref e) if self.is_const => write!(f, "(new-const {} {})", self.name.lexeme, e),
             Some(ref e) => write!(f, "(new-var {} {})", self.name.lexeme, e),
             None => write!(f, "(new-var {})", self.name.lexeme),
         }
     }
 }
 
+impl Display for DestructureStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_const {
+            write!(f, "(new-const {} {})", self.pattern, self.value)
+        } else {
+            write!(f, "(new-var {} {})", self.pattern, self.value)
+        }
+    }
+}
+
 impl Display for BlockStmt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut stmt_string = String::new();
@@ -64,9 +86,35 @@ impl Display for IfStmt {
     }
 }
 
+impl Display for YieldStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.value {
+            Some(ref v) => write!(f, "(yield {})", v),
+            None => write!(f, "(yield)"),
+        }
+    }
+}
+
+impl Display for ForInStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "(for {} in {}\n{}\n)",
+            self.name.lexeme, self.iterable, self.body
+        )
+    }
+}
+
 impl Display for WhileStmt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "(while {}\n{}\n)", self.condition, self.body)
+        match self.increment {
+            Some(ref i) => write!(
+                f,
+                "(while {}\n{}\nincrement {}\n)",
+                self.condition, self.body, i
+            ),
+            None => write!(f, "(while {}\n{}\n)", self.condition, self.body),
+        }
     }
 }
 
@@ -77,17 +125,37 @@ impl Display for FunctionStmt {
             body_string = format!("{}\n{}", body_string, s);
         }
 
-        let param_string = self
+        let mut param_string = self
             .params
             .iter()
-            .fold(String::new(), |acc, p| acc + &p.lexeme + " ");
+            .fold(String::new(), |acc, p| match p.default {
+                Some(ref default) => format!("{}{}={} ", acc, p.name.lexeme, default),
+                None => acc + &p.name.lexeme + " ",
+            });
+        if let Some(ref rest) = self.rest {
+            param_string = format!("{}...{}", param_string, rest.lexeme);
+        }
         let param_string = param_string.trim_end();
 
-        write!(
-            f,
-            "(fun-start {} ({}){}\nfun-end)",
-            self.name.lexeme, param_string, body_string
-        )
+        if self.is_getter {
+            write!(
+                f,
+                "(getter-start {}{}\ngetter-end)",
+                self.name.lexeme, body_string
+            )
+        } else if self.is_generator {
+            write!(
+                f,
+                "(generator-start {} ({}){}\ngenerator-end)",
+                self.name.lexeme, param_string, body_string
+            )
+        } else {
+            write!(
+                f,
+                "(fun-start {} ({}){}\nfun-end)",
+                self.name.lexeme, param_string, body_string
+            )
+        }
     }
 }
 
@@ -103,6 +171,9 @@ impl Display for ReturnStmt {
 impl Display for ClassStmt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut method_string = String::new();
+        for m in &self.static_methods {
+            method_string = format!("{}(static {})\n", method_string, m.borrow());
+        }
         for m in &self.methods {
             method_string = format!("{}{}\n", method_string, m.borrow());
         }
@@ -113,3 +184,84 @@ impl Display for ClassStmt {
         )
     }
 }
+
+impl Display for TraitStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut method_string = String::new();
+        for m in &self.methods {
+            method_string = format!("{}{}\n", method_string, m.borrow());
+        }
+        write!(
+            f,
+            "(trait-start {}\n{}trait-end)",
+            self.name.lexeme, method_string
+        )
+    }
+}
+
+impl Display for EnumStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut variant_string = String::new();
+        for v in &self.variants {
+            let fields: Vec<&str> = v.fields.iter().map(|f| f.lexeme.as_str()).collect();
+            variant_string = format!(
+                "{} ({} {})",
+                variant_string,
+                v.name.lexeme,
+                fields.join(" ")
+            );
+        }
+        write!(f, "(enum {}{})", self.name.lexeme, variant_string)
+    }
+}
+
+impl Display for ThrowStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(throw {})", self.value)
+    }
+}
+
+impl Display for TryStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut try_string = format!("(try\n{}\n", self.try_block);
+        if let (Some(ref name), Some(ref block)) = (&self.catch_name, &self.catch_block) {
+            try_string = format!("{}catch {}\n{}\n", try_string, name.lexeme, block);
+        }
+        if let Some(ref block) = self.finally_block {
+            try_string = format!("{}finally\n{}\n", try_string, block);
+        }
+        write!(f, "{})", try_string)
+    }
+}
+
+impl Display for ImportStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(ref alias) = self.alias {
+            write!(f, "(import {} as {})", self.path.lexeme, alias.lexeme)
+        } else {
+            let names: Vec<&str> = self.names.iter().map(|n| n.lexeme.as_str()).collect();
+            write!(f, "(import {} from {})", names.join(" "), self.path.lexeme)
+        }
+    }
+}
+
+impl Display for MatchStmt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut match_string = format!("(match {}\n", self.value);
+        for arm in &self.arms {
+            let patterns: Vec<String> = arm.patterns.iter().map(|p| p.to_string()).collect();
+            match_string = format!("{}{} => {}\n", match_string, patterns.join(", "), arm.body);
+        }
+        write!(f, "{})", match_string)
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Value(expr) => write!(f, "{}", expr),
+            Pattern::Class { class, name } => write!(f, "{} {}", class, name.lexeme),
+            Pattern::Wildcard => write!(f, "_"),
+        }
+    }
+}
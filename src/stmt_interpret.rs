@@ -1,10 +1,21 @@
 use crate::class::LoxClass;
+use crate::class::LoxTrait;
+use crate::enumeration::LoxEnum;
+use crate::enumeration::LoxVariant;
 use crate::environment::Environment;
 use crate::environment::EnvironmentTree;
+use crate::expr_interpret::call_method;
+use crate::expr_interpret::equal;
+use crate::expr_interpret::stringify;
 use crate::expr_interpret::RuntimeError;
 use crate::function::LoxFunction;
+use crate::generator::LoxGenerator;
+use crate::instance::LoxInstance;
 use crate::literal::Literal;
+use crate::module;
 use crate::statement::*;
+use crate::token::Token;
+use crate::token::TokenType;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::io;
@@ -15,19 +26,29 @@ use std::rc::Rc;
 pub enum ExecError {
     RuntimeError(RuntimeError),
     Return(Rc<Literal>),
+    Break,
+    Continue,
+    // a RuntimeError whose thrown value is always set
+    Throw(RuntimeError),
 }
 
 impl From<io::Error> for ExecError {
     fn from(error: io::Error) -> Self {
-        ExecError::RuntimeError(RuntimeError {
-            msg: format!("RuntimeError caused by an IO error: {error}"),
-        })
+        ExecError::RuntimeError(RuntimeError::without_line(format!(
+            "RuntimeError caused by an IO error: {error}"
+        )))
     }
 }
 
+// errors that carry a thrown value (e.g. out of a function call)
+// keep being thrown values
 impl From<RuntimeError> for ExecError {
     fn from(error: RuntimeError) -> Self {
-        ExecError::RuntimeError(error)
+        if error.thrown.is_some() {
+            ExecError::Throw(error)
+        } else {
+            ExecError::RuntimeError(error)
+        }
     }
 }
 
@@ -39,12 +60,24 @@ impl Stmt {
             Stmt::Expr(s) => s.execute(env, output),
             Stmt::Print(s) => s.execute(env, output),
             Stmt::Var(s) => s.execute(env, output),
+            Stmt::Destructure(s) => s.execute(env, output),
             Stmt::Block(s) => s.execute(env, output),
             Stmt::If(s) => s.execute(env, output),
             Stmt::While(s) => s.execute(env, output),
             Stmt::Return(s) => s.execute(env, output),
             Stmt::Function(s) => FunctionStmt::execute(s, env, output),
             Stmt::Class(s) => s.execute(env, output),
+            Stmt::Trait(s) => s.execute(env, output),
+            Stmt::Enum(s) => s.execute(env, output),
+            Stmt::Break(_) => Err(ExecError::Break),
+            Stmt::Continue(_) => Err(ExecError::Continue),
+            Stmt::Throw(s) => s.execute(env, output),
+            Stmt::Try(s) => s.execute(env, output),
+            Stmt::Import(s) => s.execute(env, output),
+            Stmt::Match(s) => s.execute(env, output),
+            Stmt::ForIn(s) => s.execute(env, output),
+            // resolver rejects yield outside of generators, which run it themselves
+            Stmt::Yield(_) => unreachable!(),
         }
     }
 }
@@ -52,6 +85,7 @@ impl Stmt {
 impl PrintStmt {
     pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<()> {
         let value = self.expr.eval(env, output)?;
+        let value = stringify(&value, env, output)?;
         writeln!(output, "{value}")?;
         Ok(())
     }
@@ -67,6 +101,10 @@ impl ExprStmt {
 impl VarStmt {
     pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<()> {
         match self.value {
+            Some(ref e) if self.is_const => {
+                let value = e.eval(env, output)?;
+                env.define_const(self.name.lexeme.clone(), value, self.name.line);
+            }
             Some(ref e) => {
                 let value = e.eval(env, output)?;
                 env.define(self.name.lexeme.clone(), value);
@@ -77,16 +115,33 @@ impl VarStmt {
     }
 }
 
+impl DestructureStmt {
+    pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<()> {
+        let value = self.value.eval(env, output)?;
+        let values = self.pattern.destructure(&value, env, output)?;
+        for (name, value) in self.pattern.names.iter().zip(values) {
+            if self.is_const {
+                env.define_const(name.lexeme.clone(), value, name.line);
+            } else {
+                env.define(name.lexeme.clone(), value);
+            }
+        }
+        Ok(())
+    }
+}
+
 impl BlockStmt {
     pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<()> {
         // Note that it is important to keep the invariant regarding environment
         // Otherwise it might accidentally pop the root env and panic afterwards
+        // Also, the env has to be popped even when unwinding (e.g. break)
         env.push(Environment::new());
-        for s in self.statements.iter() {
-            s.execute(env, output)?
-        }
+        let result = self
+            .statements
+            .iter()
+            .try_for_each(|s| s.execute(env, output));
         env.pop();
-        Ok(())
+        result
     }
 }
 
@@ -105,12 +160,115 @@ impl IfStmt {
 impl WhileStmt {
     pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<()> {
         while self.condition.eval(env, output)?.is_truthy() {
-            self.body.execute(env, output)?;
+            match self.body.execute(env, output) {
+                Ok(_) | Err(ExecError::Continue) => {}
+                Err(ExecError::Break) => break,
+                Err(error) => return Err(error),
+            }
+            if let Some(ref increment) = self.increment {
+                increment.eval(env, output)?;
+            }
         }
         Ok(())
     }
 }
 
+impl ForInStmt {
+    pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<()> {
+        let mut iteration = self.iteration(env, output)?;
+        while let Some(item) = iteration.next(&self.keyword, env, output)? {
+            env.push(Environment::new());
+            env.define(self.name.lexeme.clone(), item);
+            let result = self.body.execute(env, output);
+            env.pop();
+
+            match result {
+                Ok(_) | Err(ExecError::Continue) => {}
+                Err(ExecError::Break) => break,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn iteration<T: Write>(
+        &self,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Iteration> {
+        let iterable = self.iterable.eval(env, output)?;
+        // built-in sequences are copied, so the body can modify them
+        let items: Vec<Rc<Literal>> = match *iterable {
+            Literal::ListLiteral(ref l) => l.borrow().clone(),
+            Literal::MapLiteral(ref m) => {
+                m.borrow().keys().map(|k| Rc::new(k.to_literal())).collect()
+            }
+            Literal::StringLiteral(ref s) => s
+                .chars()
+                .map(|c| Rc::new(Literal::StringLiteral(c.to_string())))
+                .collect(),
+            Literal::GeneratorLiteral(ref generator) => {
+                return Ok(Iteration::Generator(generator.clone()))
+            }
+            Literal::InstanceLiteral(_) => {
+                let name = method_name("iterator", &self.keyword);
+                let iterator = call_method(&iterable, &name, vec![], env, output)?;
+                return Ok(Iteration::Iterator(iterator));
+            }
+            _ => {
+                let msg = format!(
+                    "Can only iterate over lists, maps, strings and iterable instances, got {}.",
+                    iterable
+                );
+                return Err(ExecError::RuntimeError(RuntimeError::new(
+                    &self.keyword,
+                    &msg,
+                )));
+            }
+        };
+        Ok(Iteration::Items(items.into_iter()))
+    }
+}
+
+// the state of a for-in loop between iterations
+pub enum Iteration {
+    Items(std::vec::IntoIter<Rc<Literal>>),
+    // an object with hasNext() and next()
+    Iterator(Rc<Literal>),
+    Generator(Rc<LoxGenerator>),
+}
+
+impl Iteration {
+    pub fn next<T: Write>(
+        &mut self,
+        keyword: &Token,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Option<Rc<Literal>>> {
+        match self {
+            Iteration::Items(items) => Ok(items.next()),
+            Iteration::Iterator(iterator) => {
+                let has_next = method_name("hasNext", keyword);
+                if !call_method(iterator, &has_next, vec![], env, output)?.is_truthy() {
+                    return Ok(None);
+                }
+                let next = method_name("next", keyword);
+                Ok(Some(call_method(iterator, &next, vec![], env, output)?))
+            }
+            Iteration::Generator(generator) => Ok(generator.next_value(keyword, env, output)?),
+        }
+    }
+}
+
+fn method_name(name: &str, keyword: &Token) -> Token {
+    Token::new(
+        TokenType::IDENTIFIER,
+        name.to_string(),
+        Literal::Empty,
+        keyword.line,
+    )
+}
+
 // TODO: would be nice if we can do Rc<RefCell<Self>>
 // in general FunctionStmt is a special case that I should think about
 // gettting rid of, while not losing much of its benefits if possible
@@ -140,7 +298,79 @@ impl ReturnStmt {
     }
 }
 
+impl EnumStmt {
+    pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, _output: &mut T) -> Result<()> {
+        let variants = self
+            .variants
+            .iter()
+            .map(|v| {
+                LoxVariant::new(
+                    self.name.lexeme.clone(),
+                    v.name.lexeme.clone(),
+                    v.fields.iter().map(|f| f.lexeme.clone()).collect(),
+                )
+            })
+            .collect();
+        let lox_enum = LoxEnum::new(self.name.lexeme.clone(), variants);
+        env.define(
+            self.name.lexeme.clone(),
+            Rc::new(Literal::EnumLiteral(Rc::new(lox_enum))),
+        );
+        Ok(())
+    }
+}
+
+impl TraitStmt {
+    pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, _output: &mut T) -> Result<()> {
+        let cur_env = env.keep_branch();
+        let mut methods = HashMap::new();
+        for fs in &self.methods {
+            let method = LoxFunction::new(fs.clone(), cur_env, false);
+            methods.insert(fs.borrow().name.lexeme.clone(), method);
+        }
+
+        let lox_trait = LoxTrait {
+            name: self.name.lexeme.clone(),
+            methods,
+        };
+        env.define(
+            self.name.lexeme.clone(),
+            Rc::new(Literal::TraitLiteral(Rc::new(lox_trait))),
+        );
+        Ok(())
+    }
+}
+
 impl ClassStmt {
+    // methods of the class itself take precedence over the ones of its
+    // traits, which in turn take precedence over the ones of its superclass
+    fn mix_in(
+        &self,
+        traits: &[Rc<LoxTrait>],
+        methods: &mut HashMap<String, LoxFunction>,
+    ) -> Result<()> {
+        let mut mixed_in: HashMap<&str, (&LoxTrait, &LoxFunction)> = HashMap::new();
+        for (expr, t) in self.traits.iter().zip(traits) {
+            for (name, method) in &t.methods {
+                if methods.contains_key(name) {
+                    continue;
+                }
+                if let Some((other, _)) = mixed_in.get(name.as_str()) {
+                    let msg = format!(
+                        "Method '{}' is defined by both trait '{}' and trait '{}'.",
+                        name, other.name, t.name
+                    );
+                    return Err(ExecError::RuntimeError(RuntimeError::new(&expr.name, &msg)));
+                }
+                mixed_in.insert(name, (t, method));
+            }
+        }
+        for (name, (_, method)) in mixed_in {
+            methods.insert(name.to_string(), method.clone());
+        }
+        Ok(())
+    }
+
     pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<()> {
         // get superclass
         let mut superclass = None;
@@ -155,9 +385,30 @@ impl ClassStmt {
             }
         }
 
+        // get traits
+        let mut traits = vec![];
+        for expr in &self.traits {
+            let value = expr.eval(env, output)?;
+            let Literal::TraitLiteral(ref t) = *value else {
+                return Err(ExecError::RuntimeError(RuntimeError::new(
+                    &expr.name,
+                    "Can only mix in traits.",
+                )));
+            };
+            traits.push(t.clone());
+        }
+
         // make the class itself visible to its methods
         env.define(self.name.lexeme.clone(), Rc::new(Literal::Empty));
 
+        // building static methods, which see neither 'super' nor 'this'
+        let cur_env = env.keep_branch();
+        let mut static_methods = HashMap::new();
+        for fs in &self.static_methods {
+            let method = LoxFunction::new(fs.clone(), cur_env, false);
+            static_methods.insert(fs.borrow().name.lexeme.clone(), method);
+        }
+
         // add superclass to env
         if let Some(ref sc) = superclass {
             env.push(Environment::new());
@@ -180,7 +431,14 @@ impl ClassStmt {
             env.pop();
         }
 
-        let class = LoxClass::new(self.name.lexeme.clone(), methods, superclass);
+        self.mix_in(&traits, &mut methods)?;
+
+        let class = LoxClass::new(
+            self.name.lexeme.clone(),
+            methods,
+            static_methods,
+            superclass,
+        );
         env.assign(
             &self.name,
             Rc::new(Literal::ClassLiteral(Rc::new(class))),
@@ -190,3 +448,175 @@ impl ClassStmt {
         Ok(())
     }
 }
+
+impl ThrowStmt {
+    pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<()> {
+        let value = self.value.eval(env, output)?;
+        Err(ExecError::Throw(RuntimeError::throw(&self.keyword, value)))
+    }
+}
+
+impl TryStmt {
+    pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<()> {
+        // blocks and function calls restore env by themselves while unwinding
+        let result = match self.try_block.execute(env, output) {
+            Err(ExecError::Throw(error)) if self.catch_block.is_some() => {
+                let value = error.thrown.expect("Thrown error without a value");
+                self.catch(value, env, output)
+            }
+            Err(ExecError::RuntimeError(error)) if self.catch_block.is_some() => {
+                self.catch(Self::error_instance(error, env), env, output)
+            }
+            other => other,
+        };
+
+        // finally runs no matter how the try (or catch) block ends,
+        // and whatever it unwinds with takes precedence
+        if let Some(ref finally_block) = self.finally_block {
+            finally_block.execute(env, output)?;
+        }
+        result
+    }
+
+    fn catch<T: Write>(
+        &self,
+        value: Rc<Literal>,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<()> {
+        let name = self.catch_name.as_ref().expect("Missing exception name");
+        let block = self.catch_block.as_ref().expect("Missing catch block");
+
+        env.push(Environment::new());
+        env.define(name.lexeme.clone(), value);
+        let result = block.execute(env, output);
+        env.pop();
+        result
+    }
+
+    // built-in errors are caught as instances of RuntimeError
+    // with a message and the line, which is nil if unknown
+    fn error_instance(error: RuntimeError, env: &EnvironmentTree) -> Rc<Literal> {
+        let mut instance = LoxInstance::new(env.error_class.clone());
+        instance.set(
+            "message".to_string(),
+            Rc::new(Literal::StringLiteral(error.msg)),
+        );
+        let line = match error.line {
+            Some(line) => Literal::NumberLiteral(line as f64),
+            None => Literal::Empty,
+        };
+        instance.set("line".to_string(), Rc::new(line));
+        Rc::new(Literal::InstanceLiteral(RefCell::new(instance)))
+    }
+}
+
+impl ImportStmt {
+    pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<()> {
+        let module = module::import(&self.path, env, output)?;
+        for name in &self.names {
+            match module.get(&name.lexeme, env) {
+                // named imports are bound to the module, so they stay live
+                Some(_) => env.define_import(name.lexeme.clone(), module.nid()),
+                None => {
+                    return Err(ExecError::RuntimeError(RuntimeError::new(
+                        name,
+                        &format!(
+                            "Module '{}' has no export '{}'.",
+                            module.name(),
+                            name.lexeme
+                        ),
+                    )))
+                }
+            }
+        }
+        if let Some(ref alias) = self.alias {
+            env.define(
+                alias.lexeme.clone(),
+                Rc::new(Literal::ModuleLiteral(module)),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl MatchStmt {
+    pub fn execute<T: Write>(&self, env: &mut EnvironmentTree, output: &mut T) -> Result<()> {
+        let Some((index, value)) = self.matching_arm(env, output)? else {
+            return Ok(());
+        };
+        let arm = &self.arms[index];
+        match arm.binding() {
+            Some(name) => {
+                env.push(Environment::new());
+                env.define(name.lexeme.clone(), value);
+                let result = arm.body.execute(env, output);
+                env.pop();
+                result
+            }
+            None => arm.body.execute(env, output),
+        }
+    }
+
+    // the index of the first arm that matches, along with the matched value
+    pub fn matching_arm<T: Write>(
+        &self,
+        env: &mut EnvironmentTree,
+        output: &mut T,
+    ) -> Result<Option<(usize, Rc<Literal>)>> {
+        let value = self.value.eval(env, output)?;
+        for (index, arm) in self.arms.iter().enumerate() {
+            for pattern in &arm.patterns {
+                let matched = match pattern {
+                    Pattern::Wildcard => true,
+                    Pattern::Value(expr) => {
+                        let pattern_value = expr.eval(env, output)?;
+                        match *pattern_value {
+                            Literal::ClassLiteral(ref class) => is_instance_of(&value, class),
+                            Literal::VariantLiteral(ref variant) => is_variant_of(&value, variant),
+                            _ => equal(&value, &pattern_value, env, output)?,
+                        }
+                    }
+                    Pattern::Class { class, name } => match *class.eval(env, output)? {
+                        Literal::ClassLiteral(ref c) => is_instance_of(&value, c),
+                        Literal::VariantLiteral(ref v) => is_variant_of(&value, v),
+                        _ => {
+                            return Err(ExecError::RuntimeError(RuntimeError::new(
+                                name,
+                                "Pattern with a binding must name a class or a variant.",
+                            )))
+                        }
+                    },
+                };
+                if matched {
+                    return Ok(Some((index, value)));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl MatchArm {
+    // an arm with a binding has no other patterns
+    pub fn binding(&self) -> Option<&Token> {
+        match self.patterns.first() {
+            Some(Pattern::Class { name, .. }) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+fn is_variant_of(value: &Literal, variant: &Rc<LoxVariant>) -> bool {
+    match value {
+        Literal::EnumValueLiteral(v) => v.is_variant(variant),
+        _ => false,
+    }
+}
+
+fn is_instance_of(value: &Literal, class: &LoxClass) -> bool {
+    match value {
+        Literal::InstanceLiteral(instance) => instance.borrow().class().is_subclass_of(class),
+        _ => false,
+    }
+}
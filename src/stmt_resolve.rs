@@ -1,7 +1,10 @@
+use crate::class::LoxClass;
 use crate::resolver::ClassType;
 use crate::resolver::FunctionType;
+use crate::resolver::LoopType;
 use crate::resolver::ResolutionError;
 use crate::resolver::Result;
+use crate::resolver::TryType;
 use crate::statement::*;
 use crate::{resolver::Resolver, statement::Stmt};
 use std::mem;
@@ -12,12 +15,23 @@ impl Stmt {
             Stmt::Expr(s) => s.resolve(resolver),
             Stmt::Print(s) => s.resolve(resolver),
             Stmt::Var(s) => s.resolve(resolver),
+            Stmt::Destructure(s) => s.resolve(resolver),
             Stmt::Block(s) => s.resolve(resolver),
             Stmt::If(s) => s.resolve(resolver),
             Stmt::While(s) => s.resolve(resolver),
             Stmt::Return(s) => s.resolve(resolver),
             Stmt::Function(s) => s.borrow_mut().resolve(resolver),
             Stmt::Class(s) => s.resolve(resolver),
+            Stmt::Trait(s) => s.resolve(resolver),
+            Stmt::Enum(s) => s.resolve(resolver),
+            Stmt::Break(s) => s.resolve(resolver),
+            Stmt::Continue(s) => s.resolve(resolver),
+            Stmt::Throw(s) => s.resolve(resolver),
+            Stmt::Try(s) => s.resolve(resolver),
+            Stmt::Import(s) => s.resolve(resolver),
+            Stmt::Match(s) => s.resolve(resolver),
+            Stmt::ForIn(s) => s.resolve(resolver),
+            Stmt::Yield(s) => s.resolve(resolver),
         }
     }
 }
@@ -35,7 +49,11 @@ impl BlockStmt {
 
 impl VarStmt {
     pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
-        resolver.declare(&self.name)?;
+        if self.is_const {
+            resolver.declare_const(&self.name)?;
+        } else {
+            resolver.declare(&self.name)?;
+        }
         if let Some(ref mut initializer) = self.value {
             initializer.resolve(resolver)?;
         }
@@ -44,20 +62,60 @@ impl VarStmt {
     }
 }
 
+impl DestructureStmt {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        for name in &self.pattern.names {
+            if self.is_const {
+                resolver.declare_const(name)?;
+            } else {
+                resolver.declare(name)?;
+            }
+        }
+        self.value.resolve(resolver)?;
+        for name in &self.pattern.names {
+            resolver.define(name);
+        }
+        Ok(())
+    }
+}
+
 impl FunctionStmt {
     pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
         resolver.declare(&self.name)?;
         resolver.define(&self.name);
-        self.resolve_fn(resolver, FunctionType::Fun)
+        let fun_type = if self.is_generator {
+            FunctionType::Generator
+        } else {
+            FunctionType::Fun
+        };
+        self.resolve_fn(resolver, fun_type)
     }
 
-    fn resolve_fn(&mut self, resolver: &mut Resolver, mut fun_type: FunctionType) -> Result<()> {
+    pub fn resolve_fn(
+        &mut self,
+        resolver: &mut Resolver,
+        mut fun_type: FunctionType,
+    ) -> Result<()> {
+        // a function body is never inside a loop or a try statement,
+        // even if its declaration is
+        let mut loop_type = LoopType::NonLoop;
+        let mut try_type = TryType::NonTry;
         mem::swap(&mut fun_type, &mut resolver.current_fun);
+        mem::swap(&mut loop_type, &mut resolver.current_loop);
+        mem::swap(&mut try_type, &mut resolver.current_try);
         resolver.begin_scope();
 
-        for p in &self.params {
-            resolver.declare(p)?;
-            resolver.define(p);
+        // defaults can see the params before them
+        for p in self.params.iter_mut() {
+            if let Some(ref mut default) = p.default {
+                default.resolve(resolver)?;
+            }
+            resolver.declare(&p.name)?;
+            resolver.define(&p.name);
+        }
+        if let Some(ref rest) = self.rest {
+            resolver.declare(rest)?;
+            resolver.define(rest);
         }
         for s in self.body.iter_mut() {
             s.resolve(resolver)?;
@@ -65,6 +123,8 @@ impl FunctionStmt {
 
         resolver.end_scope();
         mem::swap(&mut fun_type, &mut resolver.current_fun);
+        mem::swap(&mut loop_type, &mut resolver.current_loop);
+        mem::swap(&mut try_type, &mut resolver.current_try);
 
         Ok(())
     }
@@ -109,6 +169,12 @@ impl ReturnStmt {
                     "Can't return a value from an initializer.",
                 ));
             }
+            if resolver.current_fun == FunctionType::Generator {
+                return Err(ResolutionError::new(
+                    &self.keyword,
+                    "Can't return a value from a generator.",
+                ));
+            }
             value.resolve(resolver)?;
         }
         Ok(())
@@ -117,8 +183,58 @@ impl ReturnStmt {
 
 impl WhileStmt {
     pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        let mut loop_type = LoopType::Loop;
+        mem::swap(&mut loop_type, &mut resolver.current_loop);
+
         self.condition.resolve(resolver)?;
         self.body.resolve(resolver)?;
+        if let Some(ref mut increment) = self.increment {
+            increment.resolve(resolver)?;
+        }
+
+        mem::swap(&mut loop_type, &mut resolver.current_loop);
+        Ok(())
+    }
+}
+
+// the loop variable lives in its own scope that encloses the body,
+// which is created anew for each iteration
+impl ForInStmt {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        self.iterable.resolve(resolver)?;
+
+        let mut loop_type = LoopType::Loop;
+        mem::swap(&mut loop_type, &mut resolver.current_loop);
+        resolver.begin_scope();
+        resolver.declare(&self.name)?;
+        resolver.define(&self.name);
+        self.body.resolve(resolver)?;
+        resolver.end_scope();
+        mem::swap(&mut loop_type, &mut resolver.current_loop);
+        Ok(())
+    }
+}
+
+impl BreakStmt {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        if resolver.current_loop == LoopType::NonLoop {
+            return Err(ResolutionError::new(
+                &self.keyword,
+                "Can't use 'break' outside of a loop.",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl ContinueStmt {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        if resolver.current_loop == LoopType::NonLoop {
+            return Err(ResolutionError::new(
+                &self.keyword,
+                "Can't use 'continue' outside of a loop.",
+            ));
+        }
         Ok(())
     }
 }
@@ -134,7 +250,6 @@ impl ClassStmt {
 
         // resolve superclass if any
         if let Some(ref mut superclass) = self.superclass {
-            resolver.current_cls = ClassType::Subclass;
             if superclass.name.lexeme == self.name.lexeme {
                 return Err(ResolutionError::new(
                     &superclass.name,
@@ -143,25 +258,35 @@ impl ClassStmt {
             }
             superclass.resolve(resolver)?;
         }
+        for t in &mut self.traits {
+            t.resolve(resolver)?;
+        }
+
+        // resolve static methods, which see neither 'super' nor 'this'
+        resolver.current_cls = ClassType::Static;
+        for fs in &self.static_methods {
+            fs.borrow_mut().resolve_fn(resolver, FunctionType::Method)?;
+        }
+
+        resolver.current_cls = if self.superclass.is_some() {
+            ClassType::Subclass
+        } else {
+            ClassType::Class
+        };
 
         // define "super"
         if self.superclass.is_some() {
             resolver.begin_scope();
-            resolver
-                .peek()
-                .expect("Empty scopes")
-                .insert("super".to_string(), true);
+            resolver.define_keyword("super");
         }
 
         // define "this"
         resolver.begin_scope();
-        resolver
-            .peek()
-            .expect("Empty scopes")
-            .insert("this".to_string(), true);
+        resolver.define_keyword("this");
 
         // resolve methods
         for fs in &self.methods {
+            check_operator_method(&fs.borrow())?;
             let fun_type = if fs.borrow().name.lexeme == "init" {
                 FunctionType::Initializer
             } else {
@@ -183,3 +308,177 @@ impl ClassStmt {
         Ok(())
     }
 }
+
+impl TraitStmt {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        let mut current_cls = ClassType::Trait;
+        mem::swap(&mut current_cls, &mut resolver.current_cls);
+
+        resolver.declare(&self.name)?;
+        resolver.define(&self.name);
+
+        // methods see the 'this' of the class they are mixed into
+        resolver.begin_scope();
+        resolver.define_keyword("this");
+        for fs in &self.methods {
+            if fs.borrow().name.lexeme == "init" {
+                return Err(ResolutionError::new(
+                    &fs.borrow().name,
+                    "Can't have an initializer in a trait.",
+                ));
+            }
+            check_operator_method(&fs.borrow())?;
+            fs.borrow_mut().resolve_fn(resolver, FunctionType::Method)?;
+        }
+        resolver.end_scope();
+
+        mem::swap(&mut current_cls, &mut resolver.current_cls);
+        Ok(())
+    }
+}
+
+impl EnumStmt {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        resolver.declare(&self.name)?;
+        resolver.define(&self.name);
+
+        for (i, v) in self.variants.iter().enumerate() {
+            if self.variants[..i]
+                .iter()
+                .any(|o| o.name.lexeme == v.name.lexeme)
+            {
+                return Err(ResolutionError::new(
+                    &v.name,
+                    "Already a variant with this name in this enum.",
+                ));
+            }
+            for (j, field) in v.fields.iter().enumerate() {
+                if field.lexeme == "tag" {
+                    return Err(ResolutionError::new(field, "Can't name a field 'tag'."));
+                }
+                if v.fields[..j].iter().any(|o| o.lexeme == field.lexeme) {
+                    return Err(ResolutionError::new(
+                        field,
+                        "Already a field with this name in this variant.",
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// operators call the methods that overload them with a fixed number of arguments
+fn check_operator_method(fs: &FunctionStmt) -> Result<()> {
+    let name = &fs.name;
+    match LoxClass::operator_arity(&name.lexeme) {
+        Some(n) if !fs.arity().accepts(n) => Err(ResolutionError::new(
+            name,
+            &format!("Method '{}' must accept {} arguments.", name.lexeme, n),
+        )),
+        _ => Ok(()),
+    }
+}
+
+impl ThrowStmt {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        self.value.resolve(resolver)
+    }
+}
+
+impl YieldStmt {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        if resolver.current_fun != FunctionType::Generator {
+            return Err(ResolutionError::new(
+                &self.keyword,
+                "Can't yield outside of a generator.",
+            ));
+        }
+        if resolver.current_try == TryType::Try {
+            return Err(ResolutionError::new(
+                &self.keyword,
+                "Can't yield inside a try statement.",
+            ));
+        }
+        if let Some(ref mut value) = self.value {
+            value.resolve(resolver)?;
+        }
+        Ok(())
+    }
+}
+
+impl TryStmt {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        let mut try_type = TryType::Try;
+        mem::swap(&mut try_type, &mut resolver.current_try);
+        self.try_block.resolve(resolver)?;
+
+        // the exception lives in its own scope that encloses the catch block
+        if let Some(ref mut catch_block) = self.catch_block {
+            let name = self.catch_name.as_ref().expect("Missing exception name");
+            resolver.begin_scope();
+            resolver.declare(name)?;
+            resolver.define(name);
+            catch_block.resolve(resolver)?;
+            resolver.end_scope();
+        }
+
+        if let Some(ref mut finally_block) = self.finally_block {
+            finally_block.resolve(resolver)?;
+        }
+        mem::swap(&mut try_type, &mut resolver.current_try);
+        Ok(())
+    }
+}
+
+// the module itself is resolved separately when it gets loaded,
+// and what it imports are always globals
+impl ImportStmt {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        if !resolver.is_global_scope() {
+            return Err(ResolutionError::new(
+                &self.keyword,
+                "Can only import at the top level.",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl MatchStmt {
+    pub fn resolve(&mut self, resolver: &mut Resolver) -> Result<()> {
+        self.value.resolve(resolver)?;
+        for arm in self.arms.iter_mut() {
+            let mut binding = None;
+            let alternatives = arm.patterns.len();
+            for pattern in arm.patterns.iter_mut() {
+                match pattern {
+                    Pattern::Value(expr) => expr.resolve(resolver)?,
+                    Pattern::Class { class, name } => {
+                        class.resolve(resolver)?;
+                        binding = Some(name);
+                    }
+                    Pattern::Wildcard => {}
+                }
+            }
+
+            // the binding lives in its own scope that encloses the arm
+            if let Some(name) = binding {
+                if alternatives > 1 {
+                    return Err(ResolutionError::new(
+                        name,
+                        "Can't combine a pattern with a binding with other patterns.",
+                    ));
+                }
+                resolver.begin_scope();
+                resolver.declare(name)?;
+                resolver.define(name);
+                arm.body.resolve(resolver)?;
+                resolver.end_scope();
+            } else {
+                arm.body.resolve(resolver)?;
+            }
+        }
+        Ok(())
+    }
+}
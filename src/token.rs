@@ -3,53 +3,84 @@ use std::fmt;
 
 #[allow(clippy::upper_case_acronyms)]
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum TokenType {
     // Single-character tokens.
     LEFT_PAREN,
     RIGHT_PAREN,
     LEFT_BRACE,
     RIGHT_BRACE,
+    LEFT_BRACKET,
+    RIGHT_BRACKET,
     COMMA,
+    COLON,
     DOT,
+    DOT_DOT_DOT,
     MINUS,
     PLUS,
     SEMICOLON,
+    QUESTION,
+    QUESTION_DOT,
+    QUESTION_QUESTION,
     SLASH,
     STAR,
+    PERCENT,
 
     // One or two character tokens.
     BANG,
     BANG_EQUAL,
     EQUAL,
     EQUAL_EQUAL,
+    EQUAL_GREATER,
     GREATER,
     GREATER_EQUAL,
     LESS,
     LESS_EQUAL,
+    STAR_STAR,
+    SLASH_SLASH,
+    PLUS_EQUAL,
+    MINUS_EQUAL,
+    STAR_EQUAL,
+    SLASH_EQUAL,
+    PLUS_PLUS,
+    MINUS_MINUS,
 
     // Literals.
     IDENTIFIER,
     STRING,
+    // a string part that is followed by an interpolated expression
+    INTERPOLATION,
     NUMBER,
 
     // Keywords.
     AND,
+    BREAK,
+    CATCH,
     CLASS,
+    CONST,
+    CONTINUE,
     ELSE,
+    ENUM,
     FALSE,
+    FINALLY,
     FUN,
     FOR,
     IF,
+    IMPORT,
+    MATCH,
     NIL,
     OR,
     PRINT,
     RETURN,
     SUPER,
     THIS,
+    THROW,
+    TRAIT,
     TRUE,
+    TRY,
     VAR,
     WHILE,
+    YIELD,
 
     EOF,
 }
@@ -0,0 +1 @@
+RuntimeError: [line 14] - cannot be applied to oops, it must be a number
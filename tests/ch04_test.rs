@@ -0,0 +1,15 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("string_escape", true, true; "Escape sequences and raw strings")]
+#[test_case("invalid_escape", false, true; "Invalid escape sequence")]
+#[test_case("invalid_unicode_escape", false, true; "Invalid unicode escape sequence")]
+#[test_case("string_interpolation", true, false; "String interpolation")]
+#[test_case("unterminated_interpolation", false, true; "Unterminated string interpolation")]
+#[test_case("block_comment", true, true; "Nested block comments")]
+#[test_case("unterminated_block_comment", false, true; "Unterminated block comment")]
+fn ch04_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
@@ -0,0 +1,10 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("conditional", true, false; "Conditional expression")]
+#[test_case("missing_colon", false, true; "Conditional expression without ':'")]
+fn ch06_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
@@ -0,0 +1,10 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("arithmetic_op", true, false; "Modulo, floor division and exponent")]
+#[test_case("invalid_arithmetic_operand", true, true; "Arithmetic operators on non-numbers")]
+fn ch07_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
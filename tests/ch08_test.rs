@@ -6,6 +6,18 @@ mod common;
 #[test_case("nested_writes_to_outer_vars", true, false; "Nested writes to outer variables")]
 #[test_case("restoring_scope", true, false; "Restoring scope once a block ends")]
 #[test_case("new_var_reads_outer_shadow", false, true; "New variable reads from outer shadow")]
+#[test_case("compound_assignment", true, false; "Compound assignment, increment and decrement")]
+#[test_case("invalid_compound_assignment", false, true; "Invalid compound assignment targets")]
+#[test_case("const", true, false; "Constant declarations")]
+#[test_case("invalid_const_assignment", false, true; "Assign to a local constant")]
+#[test_case("invalid_const_compound", false, true; "Increment a local constant")]
+#[test_case("invalid_const_global", true, true; "Assign to a global constant")]
+#[test_case("missing_const_initializer", false, true; "Constant without a value")]
+#[test_case("destructuring", true, false; "Destructuring declarations and assignments")]
+#[test_case("invalid_destructuring_length", false, true; "Destructure a list that is too short")]
+#[test_case("invalid_destructuring_field", false, true; "Destructure a missing field")]
+#[test_case("invalid_destructuring_target", false, true; "Destructure into a property")]
+#[test_case("invalid_destructuring_const", false, true; "Destructure into constants")]
 fn ch08_test(test_name: &str, check_output: bool, check_error: bool) {
     run_test(test_name, check_output, check_error);
 }
@@ -6,6 +6,12 @@ mod common;
 #[test_case("loop", true, false; "While and for loops")]
 #[test_case("logical_op", true, false; "Logical operators")]
 #[test_case("if_else", true, false; "Simple if-else branching")]
+#[test_case("break_continue", true, false; "Break and continue in loops")]
+#[test_case("invalid_break", false, true; "Break outside of a loop")]
+#[test_case("invalid_continue", false, true; "Continue outside of a loop")]
+#[test_case("for_in", true, false; "For-in loops over sequences and iterators")]
+#[test_case("invalid_for_in", false, true; "For-in loop over a number")]
+#[test_case("invalid_iterator", false, true; "Iterator without hasNext")]
 fn ch09_test(test_name: &str, check_output: bool, check_error: bool) {
     run_test(test_name, check_output, check_error);
 }
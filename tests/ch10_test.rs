@@ -9,6 +9,12 @@ mod common;
 #[test_case("simple_closure", true, false; "Simple Closure")]
 #[test_case("closure", true, false; "Function returned as a closure")]
 #[test_case("simple_fun", true, false; "Simple function")]
+#[test_case("anonymous_fun", true, false; "Anonymous function expressions")]
+#[test_case("default_params", true, false; "Default and rest parameters")]
+#[test_case("invalid_arity_range", true, true; "Too many arguments for default parameters")]
+#[test_case("invalid_rest_arity", false, true; "Too few arguments for rest parameters")]
+#[test_case("invalid_default_param", false, true; "Parameter without default after default")]
+#[test_case("invalid_rest_param", false, true; "Rest parameter is not the last")]
 fn ch10_test(test_name: &str, check_output: bool, check_error: bool) {
     run_test(test_name, check_output, check_error);
 }
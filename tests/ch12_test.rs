@@ -8,6 +8,9 @@ mod common;
 #[test_case("invalid_this", false, true; "Invalid use of this")]
 #[test_case("instance_methods", true, false; "Calling methods of an instance")]
 #[test_case("instance_fields", true, true; "Getting and setting fields of an instance")]
+#[test_case("static_methods", true, true; "Static class methods")]
+#[test_case("invalid_this_in_static_method", false, true; "Invalid use of this in a static method")]
+#[test_case("getters", true, false; "Getter methods")]
 fn ch12_test(test_name: &str, check_output: bool, check_error: bool) {
     run_test(test_name, check_output, check_error);
 }
@@ -0,0 +1 @@
+RuntimeError: [line 1] Error in module 'tests/modules/cycle_a.lox': [line 1] Error in module 'tests/modules/cycle_b.lox': [line 1] Circular import of module 'tests/modules/cycle_a.lox'.
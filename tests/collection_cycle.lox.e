@@ -0,0 +1 @@
+RuntimeError: [line 18] - cannot be applied to [1, [...]] and 1, both must be number
@@ -0,0 +1,16 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("list", true, false; "List literals, indexing and natives")]
+#[test_case("list_index_out_of_range", true, true; "List index out of range")]
+#[test_case("list_index_not_integer", false, true; "List index is not an integer")]
+#[test_case("map", true, false; "Map literals, indexing and natives")]
+#[test_case("map_missing_key", true, true; "Map key is missing")]
+#[test_case("map_invalid_key", false, true; "Map key is not hashable")]
+#[test_case("native_map_invalid_key", true, true; "Map key passed to a native is not hashable")]
+#[test_case("collection_cycle", true, true; "Lists and maps that contain themselves")]
+fn collection_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
@@ -10,6 +10,7 @@ pub fn run_test(test_name: &str, check_output: bool, expect_error: bool) {
     let mut error_output = Vec::new();
 
     let mut it = Interpreter::new();
+    it.set_file_path(&file_name);
     match it.run(input, &mut output, &mut error_output, false) {
         Ok(_) | Err(_) => {
             let output = String::from_utf8(output).expect("Not UTF-8");
@@ -0,0 +1,13 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("enums", true, false; "Enums with payloads")]
+#[test_case("invalid_enum_arity", false, true; "Construct a variant with too few fields")]
+#[test_case("invalid_enum_variant", true, true; "Undefined variant")]
+#[test_case("invalid_enum_field", false, true; "Field named tag")]
+#[test_case("invalid_enum_duplicate", false, true; "Duplicate variant")]
+fn enum_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
@@ -0,0 +1,11 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("exceptions", true, false; "Throw, try, catch and finally")]
+#[test_case("uncaught_exception", true, true; "Uncaught exception")]
+#[test_case("invalid_try", false, true; "Try without catch or finally")]
+fn exception_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
@@ -0,0 +1,14 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("generators", true, false; "Generators with yield")]
+#[test_case("generator_error", true, false; "Runtime error within a generator")]
+#[test_case("invalid_yield", false, true; "Yield outside of a generator")]
+#[test_case("invalid_yield_in_try", false, true; "Yield inside a try statement")]
+#[test_case("invalid_generator_return", false, true; "Return a value from a generator")]
+#[test_case("invalid_generator_reentry", true, true; "Resume a running generator")]
+fn generator_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
@@ -0,0 +1 @@
+RuntimeError: [line 2] % cannot be applied to a and 2, both must be number
@@ -0,0 +1 @@
+RuntimeError: [line 6] Expected 1 to 2 arguments but got 3.
@@ -0,0 +1 @@
+ResolutionError: [line 3] Can't use 'break' outside of a loop.
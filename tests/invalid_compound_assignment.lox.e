@@ -0,0 +1,2 @@
+ParsingError: [line 2] Error at '+=': Invalid assignment target.
+ParsingError: [line 3] Error at '++': Invalid assignment target.
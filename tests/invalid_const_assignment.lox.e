@@ -0,0 +1 @@
+ResolutionError: [line 4] Can't assign to constant 'x' declared on line 2.
@@ -0,0 +1 @@
+ResolutionError: [line 3] Can't assign to constant 'n' declared on line 2.
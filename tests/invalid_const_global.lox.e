@@ -0,0 +1 @@
+RuntimeError: [line 5] Can't assign to constant 'answer' declared on line 1.
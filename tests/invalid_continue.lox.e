@@ -0,0 +1 @@
+ResolutionError: [line 1] Can't use 'continue' outside of a loop.
@@ -0,0 +1 @@
+ParsingError: [line 1] Error at 'b': Parameter without a default can't follow one with a default.
@@ -0,0 +1 @@
+ResolutionError: [line 3] Can't assign to constant 'a' declared on line 2.
@@ -0,0 +1 @@
+RuntimeError: [line 6] Undefined property 'y'.
@@ -0,0 +1 @@
+RuntimeError: [line 1] Expected at least 3 elements to destructure but got 2.
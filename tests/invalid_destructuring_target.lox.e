@@ -0,0 +1 @@
+ParsingError: [line 3] Error at '=': Invalid assignment target.
@@ -0,0 +1 @@
+RuntimeError: [line 2] Expected 2 arguments but got 1.
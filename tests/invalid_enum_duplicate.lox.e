@@ -0,0 +1 @@
+ResolutionError: [line 1] Already a variant with this name in this enum.
@@ -0,0 +1 @@
+ResolutionError: [line 1] Can't name a field 'tag'.
@@ -0,0 +1 @@
+RuntimeError: [line 3] Undefined variant 'Square'.
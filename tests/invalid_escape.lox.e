@@ -0,0 +1 @@
+ScanningError: [line 3] Error: Invalid escape sequence '\q'
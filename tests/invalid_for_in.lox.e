@@ -0,0 +1 @@
+RuntimeError: [line 1] Can only iterate over lists, maps, strings and iterable instances, got 42.
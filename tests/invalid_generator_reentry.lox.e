@@ -0,0 +1 @@
+RuntimeError: [line 4] Generator is already running.
@@ -0,0 +1 @@
+ResolutionError: [line 3] Can't return a value from a generator.
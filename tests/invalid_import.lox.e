@@ -0,0 +1 @@
+ResolutionError: [line 2] Can only import at the top level.
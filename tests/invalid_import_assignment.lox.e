@@ -0,0 +1 @@
+RuntimeError: [line 3] Can't assign to imported variable 'count'.
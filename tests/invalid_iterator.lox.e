@@ -0,0 +1 @@
+RuntimeError: [line 7] Can't call 'hasNext' on 42.
@@ -0,0 +1 @@
+RuntimeError: [line 4] Pattern with a binding must name a class or a variant.
@@ -0,0 +1 @@
+ResolutionError: [line 5] Can't combine a pattern with a binding with other patterns.
@@ -0,0 +1 @@
+ResolutionError: [line 2] Method '__add__' must accept 1 arguments.
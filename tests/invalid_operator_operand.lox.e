@@ -0,0 +1 @@
+RuntimeError: [line 13] * cannot be applied to instance of class Point and 2, both must be number
@@ -0,0 +1 @@
+ParsingError: [line 2] Error at '=': Invalid assignment target.
@@ -0,0 +1 @@
+RuntimeError: [line 4] Only instances, classes and modules have properties.
@@ -0,0 +1 @@
+RuntimeError: [line 1] Argument must be a string, got 1.
@@ -0,0 +1 @@
+RuntimeError: [line 2] Undefined property 'reverse'.
@@ -0,0 +1 @@
+RuntimeError: [line 13] + cannot be applied to instance of class Name, must be two numbers or two strings
@@ -0,0 +1 @@
+RuntimeError: [line 5] Expected at least 2 arguments but got 1.
@@ -0,0 +1 @@
+ParsingError: [line 1] Error at ',': Rest parameter must be the last parameter.
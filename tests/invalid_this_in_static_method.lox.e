@@ -0,0 +1 @@
+ResolutionError: [line 4] Can't use 'this' in a static method.
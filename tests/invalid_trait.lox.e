@@ -0,0 +1 @@
+RuntimeError: [line 2] Can only mix in traits.
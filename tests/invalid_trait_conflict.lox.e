@@ -0,0 +1 @@
+RuntimeError: [line 14] Method 'move' is defined by both trait 'Walker' and trait 'Swimmer'.
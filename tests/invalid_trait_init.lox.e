@@ -0,0 +1 @@
+ResolutionError: [line 2] Can't have an initializer in a trait.
@@ -0,0 +1 @@
+ResolutionError: [line 3] Can't use 'super' in a trait.
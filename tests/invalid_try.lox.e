@@ -0,0 +1 @@
+ParsingError: [line 1] Error at 'try': Expect 'catch' or 'finally' after try block.
@@ -0,0 +1 @@
+ScanningError: [line 1] Error: Invalid unicode code point '\u{110000}'
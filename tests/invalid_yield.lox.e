@@ -0,0 +1 @@
+ResolutionError: [line 2] Can't yield outside of a generator.
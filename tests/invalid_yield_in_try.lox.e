@@ -0,0 +1 @@
+ResolutionError: [line 3] Can't yield inside a try statement.
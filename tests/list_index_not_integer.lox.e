@@ -0,0 +1 @@
+RuntimeError: [line 3] List index must be an integer, got 1.5.
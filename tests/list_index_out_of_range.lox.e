@@ -0,0 +1 @@
+RuntimeError: [line 3] Index 3 is out of range for a list of length 3.
@@ -0,0 +1 @@
+RuntimeError: [line 1] Map key must be a string, number or boolean, got [1].
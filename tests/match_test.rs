@@ -0,0 +1,11 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("match", true, false; "Match literal and class patterns")]
+#[test_case("invalid_match_pattern", false, true; "Combine a binding with other patterns")]
+#[test_case("invalid_match_class", false, true; "Bind a pattern that is not a class")]
+fn match_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
@@ -0,0 +1 @@
+ParsingError: [line 2] Error at ';': Expect ':' after then branch of conditional expression.
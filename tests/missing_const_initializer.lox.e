@@ -0,0 +1 @@
+ParsingError: [line 1] Error at ';': Expect '=' after constant name.
@@ -0,0 +1 @@
+RuntimeError: [line 1] Module 'tests/modules/util.lox' has no export 'cube'.
@@ -0,0 +1 @@
+RuntimeError: [line 2] Error in module 'tests/modules/broken.lox': [line 3] Undefined variable 'undefined'
@@ -0,0 +1 @@
+RuntimeError: [line 2 in module 'tests/modules/boom.lox'] + cannot be applied to 1, must be two numbers or two strings
@@ -0,0 +1,17 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("modules", true, false; "Import modules and their exports")]
+#[test_case("circular_import", false, true; "Circular import")]
+#[test_case("module_error", true, true; "Runtime error in a module")]
+#[test_case("module_function_error", true, true; "Runtime error in a module function")]
+#[test_case("missing_export", true, true; "Import a missing export")]
+#[test_case("invalid_import", false, true; "Import within a block")]
+#[test_case("live_exports", true, false; "Exports see updates made by the module")]
+#[test_case("module_isolation", true, false; "Modules don't see the importer's globals")]
+#[test_case("invalid_import_assignment", false, true; "Assign to an imported variable")]
+fn module_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
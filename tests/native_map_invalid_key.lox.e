@@ -0,0 +1 @@
+RuntimeError: [line 3] [@native-fn-has] Map key must be a string, number or boolean, got [1].
@@ -0,0 +1,17 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("operator_overloading", true, false; "Operators overloaded by methods")]
+#[test_case("invalid_operator_operand", true, true; "Operator without a method")]
+#[test_case("invalid_reflected_operand", true, true; "Left operand without a reflected method")]
+#[test_case("invalid_operator_method", false, true; "Operator method with wrong arity")]
+#[test_case("optional_chaining", true, false; "Optional chaining and nil coalescing")]
+#[test_case("invalid_optional_chaining", true, true; "Optional chain ended by parentheses")]
+#[test_case("invalid_optional_assignment", false, true; "Assign through optional chaining")]
+#[test_case("invalid_optional_nested_get", false, true; "Assign to a property after optional chaining")]
+#[test_case("invalid_optional_nested_index", false, true; "Assign to an index after optional chaining")]
+fn operator_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
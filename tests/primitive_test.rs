@@ -0,0 +1,11 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("primitive_methods", true, false; "Methods on strings and numbers")]
+#[test_case("invalid_primitive_method", true, true; "Undefined method on a string")]
+#[test_case("invalid_primitive_argument", false, true; "Wrong argument type for a string method")]
+fn primitive_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
@@ -0,0 +1 @@
+RuntimeError: [line 30] Undefined property 'square'.
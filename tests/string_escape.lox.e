@@ -0,0 +1 @@
+RuntimeError: [line 14] - cannot be applied to line  and 13, both must be number
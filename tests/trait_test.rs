@@ -0,0 +1,13 @@
+use common::run_test;
+use test_case::test_case;
+
+mod common;
+
+#[test_case("traits", true, false; "Traits mixed into classes")]
+#[test_case("invalid_trait_conflict", true, true; "Two traits with the same method")]
+#[test_case("invalid_trait", false, true; "Mix in a class")]
+#[test_case("invalid_trait_super", false, true; "Use super in a trait")]
+#[test_case("invalid_trait_init", false, true; "Initializer in a trait")]
+fn trait_test(test_name: &str, check_output: bool, check_error: bool) {
+    run_test(test_name, check_output, check_error);
+}
@@ -0,0 +1 @@
+RuntimeError: [line 2] Uncaught exception: unhandled
@@ -0,0 +1 @@
+ScanningError: [line 2] Error: Unterminated block comment